@@ -0,0 +1,51 @@
+use test::{Bencher, black_box};
+
+use conversion::*;
+use keysize::*;
+use util::*;
+
+fn data(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i * 31 + 7) as u8).collect()
+}
+
+#[bench]
+fn bench_xor(b: &mut Bencher) {
+    let (x, y) = (data(4096), data(4096));
+    b.bytes = 4096;
+    b.iter(|| xor(black_box(&x), black_box(&y)));
+}
+
+#[bench]
+fn bench_xor_into(b: &mut Bencher) {
+    let (mut x, y) = (data(4096), data(4096));
+    b.bytes = 4096;
+    b.iter(|| xor_into(black_box(&mut x), black_box(&y)));
+}
+
+#[bench]
+fn bench_xor_cycle(b: &mut Bencher) {
+    let mut x = data(4096);
+    b.bytes = 4096;
+    b.iter(|| xor_cycle(black_box(&mut x), b"Terminator X: Bring the noise"));
+}
+
+#[bench]
+fn bench_xor_key(b: &mut Bencher) {
+    let x = data(4096);
+    b.bytes = 4096;
+    b.iter(|| xor_key(black_box(&x), "Terminator X: Bring the noise").unwrap());
+}
+
+#[bench]
+fn bench_hamming(b: &mut Bencher) {
+    let (x, y) = (data(4096), data(4096));
+    b.bytes = 4096;
+    b.iter(|| hamming(black_box(&x), black_box(&y)));
+}
+
+#[bench]
+fn bench_hamming_keysizes(b: &mut Bencher) {
+    let Base64(block) = read_base64_file("data/6.txt");
+    b.bytes = block.len() as u64;
+    b.iter(|| hamming_keysizes(black_box(&block), 2..41));
+}
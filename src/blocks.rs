@@ -0,0 +1,133 @@
+use std::fmt;
+use std::collections::HashMap;
+
+use conversion::*;
+
+/// indices of every block that occurs more than once, grouped by contents in
+/// order of first appearance. panics if `block_size` is zero.
+pub fn repeated_blocks(v: &[u8], block_size: usize) -> Vec<Vec<usize>> {
+    assert!(block_size > 0);
+    let mut seen: HashMap<&[u8], usize> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (i, block) in v.chunks(block_size).enumerate() {
+        let group = *seen.entry(block).or_insert(groups.len());
+        if group == groups.len() {
+            groups.push(vec![]);
+        }
+        groups[group].push(i);
+    }
+    groups.into_iter().filter(|g| g.len() > 1).collect()
+}
+
+/// renders a buffer as numbered blocks, labelling repeated blocks so ECB
+/// structure stands out.
+pub struct BlockView<'a> {
+    data: &'a [u8],
+    block_size: usize,
+}
+
+impl<'a> BlockView<'a> {
+    pub fn new(data: &'a [u8], block_size: usize) -> BlockView<'a> {
+        assert!(block_size > 0);
+        BlockView { data: data, block_size: block_size }
+    }
+}
+
+// A, B, ... Z, AA, AB, ...
+fn label(mut n: usize) -> String {
+    let mut result = vec![];
+    loop {
+        result.push((b'A' + (n % 26) as u8) as char);
+        if n < 26 {
+            break;
+        }
+        n = n / 26 - 1;
+    }
+    result.iter().rev().cloned().collect()
+}
+
+impl<'a> fmt::Display for BlockView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut labels = HashMap::new();
+        for (n, group) in repeated_blocks(self.data, self.block_size).iter().enumerate() {
+            for &i in group {
+                labels.insert(i, label(n));
+            }
+        }
+        for (i, block) in self.data.chunks(self.block_size).enumerate() {
+            let hex = HEX_LOWER.encode(block);
+            let offset = i * self.block_size;
+            match labels.get(&i) {
+                Some(l) => try!(writeln!(f, "{:>4}  {:08x}  {:<width$}  [{}]", i, offset, hex, l,
+                                         width = self.block_size * 2)),
+                None => try!(writeln!(f, "{:>4}  {:08x}  {}", i, offset, hex))
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockChange {
+    Same,
+    Changed,
+    /// only present in the second buffer.
+    Added,
+    /// only present in the first buffer.
+    Removed,
+}
+
+/// compares two buffers block by block, e.g. ciphertexts before and after an
+/// oracle query. panics if `block_size` is zero.
+pub fn block_changes(a: &[u8], b: &[u8], block_size: usize) -> Vec<BlockChange> {
+    assert!(block_size > 0);
+    let first: Vec<&[u8]> = a.chunks(block_size).collect();
+    let second: Vec<&[u8]> = b.chunks(block_size).collect();
+    let n = ::std::cmp::max(first.len(), second.len());
+    (0..n).map(|i| {
+        match (first.get(i), second.get(i)) {
+            (Some(x), Some(y)) if x == y => BlockChange::Same,
+            (Some(_), Some(_)) => BlockChange::Changed,
+            (None, Some(_)) => BlockChange::Added,
+            _ => BlockChange::Removed,
+        }
+    }).collect()
+}
+
+/// renders `block_changes` with both sides of each changed block.
+pub struct BlockDiff<'a> {
+    first: &'a [u8],
+    second: &'a [u8],
+    block_size: usize,
+}
+
+impl<'a> BlockDiff<'a> {
+    pub fn new(first: &'a [u8], second: &'a [u8], block_size: usize) -> BlockDiff<'a> {
+        assert!(block_size > 0);
+        BlockDiff { first: first, second: second, block_size: block_size }
+    }
+}
+
+impl<'a> fmt::Display for BlockDiff<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let first: Vec<&[u8]> = self.first.chunks(self.block_size).collect();
+        let second: Vec<&[u8]> = self.second.chunks(self.block_size).collect();
+        let changes = block_changes(self.first, self.second, self.block_size);
+        for (i, change) in changes.iter().enumerate() {
+            let offset = i * self.block_size;
+            match *change {
+                BlockChange::Same =>
+                    try!(writeln!(f, "  {:>4}  {:08x}  {}", i, offset, HEX_LOWER.encode(first[i]))),
+                BlockChange::Changed => {
+                    try!(writeln!(f, "- {:>4}  {:08x}  {}", i, offset, HEX_LOWER.encode(first[i])));
+                    try!(writeln!(f, "+ {:>4}  {:08x}  {}", i, offset, HEX_LOWER.encode(second[i])));
+                }
+                BlockChange::Added =>
+                    try!(writeln!(f, "+ {:>4}  {:08x}  {}", i, offset, HEX_LOWER.encode(second[i]))),
+                BlockChange::Removed =>
+                    try!(writeln!(f, "- {:>4}  {:08x}  {}", i, offset, HEX_LOWER.encode(first[i]))),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::{error, fmt, iter};
+#[cfg(feature = "openssl")]
+use openssl::crypto::symm::{Crypter, Mode, Type};
+
+use aes::*;
+
+/// a keyed block cipher. the key is set up once, when the cipher is created.
+pub trait BlockCipher {
+    fn block_size(&self) -> usize;
+
+    /// `block` must be exactly `block_size` bytes.
+    fn encrypt_block(&self, block: &mut [u8]);
+
+    /// `block` must be exactly `block_size` bytes.
+    fn decrypt_block(&self, block: &mut [u8]);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CipherError {
+    /// the key can't be this many bytes long.
+    InvalidKeyLength(usize),
+    /// the IV must be a single block, not this many bytes.
+    InvalidIvLength(usize),
+    /// a CTR counter is over eight bytes or doesn't fill a block with its nonce.
+    InvalidLayout,
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CipherError::InvalidKeyLength(n) => write!(f, "invalid key length {}", n),
+            CipherError::InvalidIvLength(n) => write!(f, "invalid IV length {}", n),
+            CipherError::InvalidLayout => write!(f, "invalid counter block layout"),
+        }
+    }
+}
+
+impl error::Error for CipherError {
+    fn description(&self) -> &str {
+        match *self {
+            CipherError::InvalidKeyLength(..) => "invalid key length",
+            CipherError::InvalidIvLength(..) => "invalid IV length",
+            CipherError::InvalidLayout => "invalid counter block layout",
+        }
+    }
+}
+
+impl<C: BlockCipher + ?Sized> BlockCipher for Box<C> {
+    fn block_size(&self) -> usize {
+        (**self).block_size()
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        (**self).encrypt_block(block)
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        (**self).decrypt_block(block)
+    }
+}
+
+/// AES-128, 192 or 256 depending on the length of `key`. OpenSSL 0.7 has no
+/// AES-192, so 24 byte keys always use the pure Rust `Aes`.
+pub fn aes_cipher(key: &[u8]) -> Result<Box<BlockCipher>, CipherError> {
+    match key.len() {
+        16 => Ok(Box::new(Aes128::new(key))),
+        24 => Ok(Box::new(Aes::new(key))),
+        32 => Ok(Box::new(Aes256::new(key))),
+        n => Err(CipherError::InvalidKeyLength(n))
+    }
+}
+
+/// AES via OpenSSL, with one context per direction kept for the lifetime of
+/// the key.
+#[cfg(feature = "openssl")]
+struct Openssl {
+    encrypter: Crypter,
+    decrypter: Crypter,
+}
+
+#[cfg(feature = "openssl")]
+impl Openssl {
+    fn new(key: &[u8]) -> Openssl {
+        let t = match key.len() {
+            16 => Type::AES_128_ECB,
+            32 => Type::AES_256_ECB,
+            n => panic!("no OpenSSL AES for {} byte keys", n)
+        };
+        let iv: Vec<u8> = iter::repeat(0).take(16).collect();
+        let encrypter = Crypter::new(t);
+        encrypter.init(Mode::Encrypt, key, &iv);
+        encrypter.pad(false);
+        let decrypter = Crypter::new(t);
+        decrypter.init(Mode::Decrypt, key, &iv);
+        decrypter.pad(false);
+        Openssl { encrypter: encrypter, decrypter: decrypter }
+    }
+}
+
+#[cfg(feature = "openssl")]
+impl BlockCipher for Openssl {
+    fn block_size(&self) -> usize {
+        16
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        assert!(block.len() == 16);
+        let result = self.encrypter.update(block);
+        block.copy_from_slice(&result);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        assert!(block.len() == 16);
+        let result = self.decrypter.update(block);
+        block.copy_from_slice(&result);
+    }
+}
+
+#[cfg(feature = "openssl")]
+type Backend = Openssl;
+
+#[cfg(not(feature = "openssl"))]
+type Backend = Aes;
+
+/// AES-128 via OpenSSL, or the pure Rust `Aes` without the `openssl` feature.
+pub struct Aes128(Backend);
+
+/// AES-256 via OpenSSL, or the pure Rust `Aes` without the `openssl` feature.
+pub struct Aes256(Backend);
+
+impl Aes128 {
+    pub fn new(key: &[u8]) -> Aes128 {
+        assert!(key.len() == 16);
+        Aes128(Backend::new(key))
+    }
+}
+
+impl Aes256 {
+    pub fn new(key: &[u8]) -> Aes256 {
+        assert!(key.len() == 32);
+        Aes256(Backend::new(key))
+    }
+}
+
+impl BlockCipher for Aes128 {
+    fn block_size(&self) -> usize {
+        16
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        self.0.encrypt_block(block)
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        self.0.decrypt_block(block)
+    }
+}
+
+impl BlockCipher for Aes256 {
+    fn block_size(&self) -> usize {
+        16
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        self.0.encrypt_block(block)
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        self.0.decrypt_block(block)
+    }
+}
@@ -1,137 +1,1091 @@
 
 use std::io::prelude::*;
+use std::io::BufReader;
 use std::fs::File;
+use std::{error, fmt, io, ops, str, string};
+
+use stream::*;
 
 pub const ALPHABET: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 !\"#$%&'()*+,-./:;<=>?@{|}~";
 pub const HEX_ALPHABET: &'static str = "0123456789ABCDEF";
+pub const HEX_LOWER_ALPHABET: &'static str = "0123456789abcdef";
 pub const BASE64_ALPHABET: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+pub const BASE64_URL_SAFE_ALPHABET: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+pub const BASE32_ALPHABET: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+// RFC 3986 unreserved characters, which percent encoding leaves alone.
+pub const URL_UNRESERVED: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
 
+/// decoded bytes that display as Base64.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Base64(pub Vec<u8>);
+
+/// decoded bytes that display as hex.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Hex(pub Vec<u8>);
 
+/// decoded bytes that display as Base32.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Base32(pub Vec<u8>);
+
+/// decoded bytes that display as Ascii85.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Ascii85(pub Vec<u8>);
+
+/// decoded bytes that display percent encoded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Percent(pub Vec<u8>);
+
+/// an arbitrary byte buffer, converted to and from text without loss.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Bytes(pub Vec<u8>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaddingPolicy {
+    /// always emit '=' padding and reject input without it.
+    Required,
+    /// emit '=' padding but accept input with or without it.
+    Optional,
+    /// never emit '=' padding and reject input with it.
+    Omitted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Base64Config {
+    pub alphabet: &'static str,
+    pub padding: PaddingPolicy,
+    /// wrap encoded output after this many characters.
+    pub line_width: Option<usize>,
+    pub line_ending: &'static str,
+    /// skip spaces, tabs and line breaks (both '\n' and '\r\n') when decoding.
+    pub ignore_whitespace: bool,
+}
+
+pub const BASE64_STANDARD: Base64Config = Base64Config {
+    alphabet: BASE64_ALPHABET,
+    padding: PaddingPolicy::Required,
+    line_width: None,
+    line_ending: "\n",
+    ignore_whitespace: false,
+};
+
+// as found in web tokens and cookies.
+pub const BASE64_URL_SAFE: Base64Config = Base64Config {
+    alphabet: BASE64_URL_SAFE_ALPHABET,
+    padding: PaddingPolicy::Omitted,
+    line_width: None,
+    line_ending: "\n",
+    ignore_whitespace: false,
+};
+
+// RFC 2045 transfer encoding.
+pub const BASE64_MIME: Base64Config = Base64Config {
+    alphabet: BASE64_ALPHABET,
+    padding: PaddingPolicy::Required,
+    line_width: Some(76),
+    line_ending: "\r\n",
+    ignore_whitespace: true,
+};
+
+// PEM armour, RFC 7468.
+pub const BASE64_PEM: Base64Config = Base64Config {
+    alphabet: BASE64_ALPHABET,
+    padding: PaddingPolicy::Required,
+    line_width: Some(64),
+    line_ending: "\n",
+    ignore_whitespace: true,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Case {
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HexConfig {
+    pub case: Case,
+    /// placed between each group of bytes.
+    pub separator: &'static str,
+    /// bytes per group; zero puts everything in one group.
+    pub group_size: usize,
+}
+
+pub const HEX_UPPER: HexConfig = HexConfig {
+    case: Case::Upper,
+    separator: "",
+    group_size: 0,
+};
+
+pub const HEX_LOWER: HexConfig = HexConfig {
+    case: Case::Lower,
+    separator: "",
+    group_size: 0,
+};
+
+// as used for certificate fingerprints.
+pub const HEX_COLON: HexConfig = HexConfig {
+    case: Case::Lower,
+    separator: ":",
+    group_size: 1,
+};
+
+pub const HEX_SPACED: HexConfig = HexConfig {
+    case: Case::Lower,
+    separator: " ",
+    group_size: 1,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecodeError {
+    /// a character outside of the alphabet, and where it was found.
+    InvalidCharacter(char, usize),
+    /// padding that is misplaced or does not complete its group.
+    InvalidPadding(char, usize),
+    /// the input ended partway through a group.
+    TruncatedGroup(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidCharacter(c, n) =>
+                write!(f, "invalid character {:?} at offset {}", c, n),
+            DecodeError::InvalidPadding(c, n) =>
+                write!(f, "invalid padding {:?} at offset {}", c, n),
+            DecodeError::TruncatedGroup(n) =>
+                write!(f, "truncated group at offset {}", n),
+        }
+    }
+}
+
+impl error::Error for DecodeError {
+    fn description(&self) -> &str {
+        match *self {
+            DecodeError::InvalidCharacter(..) => "invalid character",
+            DecodeError::InvalidPadding(..) => "invalid padding",
+            DecodeError::TruncatedGroup(..) => "truncated group",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Decode(DecodeError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadError::Io(ref e) => write!(f, "{}", e),
+            ReadError::Decode(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for ReadError {
+    fn description(&self) -> &str {
+        match *self {
+            ReadError::Io(ref e) => e.description(),
+            ReadError::Decode(ref e) => e.description(),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> ReadError {
+        // the streaming decoders report format errors through io::Error.
+        let decode = e.get_ref().and_then(|inner| inner.downcast_ref::<DecodeError>()).cloned();
+        match decode {
+            Some(d) => ReadError::Decode(d),
+            None => ReadError::Io(e)
+        }
+    }
+}
+
+impl From<DecodeError> for ReadError {
+    fn from(e: DecodeError) -> ReadError {
+        ReadError::Decode(e)
+    }
+}
+
 pub fn ascii_single_keys() -> Vec<u8> {
     ALPHABET.chars().map(|c| c as u8).collect()
 }
 
-fn hex_to_int(c: char) -> u32 {
-    assert!(c.is_digit(16));
-    c.to_digit(16).unwrap()
+fn hex_to_int(c: char, offset: usize) -> Result<u8, DecodeError> {
+    match c.to_digit(16) {
+        Some(n) => Ok(n as u8),
+        None => Err(DecodeError::InvalidCharacter(c, offset))
+    }
+}
+
+fn base64_to_int(alphabet: &str, c: char, offset: usize) -> Result<u8, DecodeError> {
+    match alphabet.find(c) {
+        Some(n) => Ok(n as u8),
+        None => Err(DecodeError::InvalidCharacter(c, offset))
+    }
+}
+
+impl Bytes {
+    pub fn from_utf8(s: &str) -> Bytes {
+        Bytes(s.as_bytes().to_vec())
+    }
+
+    pub fn to_utf8(&self) -> Result<String, string::FromUtf8Error> {
+        String::from_utf8(self.0.clone())
+    }
+
+    pub fn to_utf8_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    /// each char must be in U+0000..U+00FF and maps to the byte of the same value.
+    pub fn from_latin1(s: &str) -> Result<Bytes, DecodeError> {
+        let mut result = Vec::with_capacity(s.len());
+        for (i, c) in s.chars().enumerate() {
+            if (c as u32) > 0xff {
+                return Err(DecodeError::InvalidCharacter(c, i));
+            }
+            result.push(c as u8);
+        }
+        Ok(Bytes(result))
+    }
+
+    pub fn to_latin1(&self) -> String {
+        self.0.iter().map(|&x| x as char).collect()
+    }
+
+    /// parse the output of `to_escaped`.
+    pub fn from_escaped(s: &str) -> Result<Bytes, DecodeError> {
+        let chars: Vec<char> = s.chars().collect();
+        let mut result = Vec::with_capacity(chars.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c != '\\' {
+                if !(c >= ' ' && c <= '~') {
+                    return Err(DecodeError::InvalidCharacter(c, i));
+                }
+                result.push(c as u8);
+                i += 1;
+                continue;
+            }
+            match chars.get(i + 1) {
+                Some(&'\\') => result.push(b'\\'),
+                Some(&'n') => result.push(b'\n'),
+                Some(&'r') => result.push(b'\r'),
+                Some(&'t') => result.push(b'\t'),
+                Some(&'x') => {
+                    if i + 3 >= chars.len() {
+                        return Err(DecodeError::TruncatedGroup(i));
+                    }
+                    let high = try!(hex_to_int(chars[i + 2], i + 2));
+                    let low = try!(hex_to_int(chars[i + 3], i + 3));
+                    result.push(16 * high + low);
+                    i += 2;
+                }
+                Some(&c) => return Err(DecodeError::InvalidCharacter(c, i + 1)),
+                None => return Err(DecodeError::TruncatedGroup(i)),
+            }
+            i += 2;
+        }
+        Ok(Bytes(result))
+    }
+
+    /// printable ascii as-is, backslash and common whitespace as C escapes,
+    /// and everything else as `\xNN`.
+    pub fn to_escaped(&self) -> String {
+        let mut result = String::with_capacity(self.0.len());
+        for &x in self.0.iter() {
+            match x {
+                b'\\' => result.push_str("\\\\"),
+                b'\n' => result.push_str("\\n"),
+                b'\r' => result.push_str("\\r"),
+                b'\t' => result.push_str("\\t"),
+                0x20...0x7e => result.push(x as char),
+                _ => result.push_str(&format!("\\x{:02x}", x)),
+            }
+        }
+        result
+    }
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_escaped())
+    }
 }
 
 pub fn raw_to_string(v: &Vec<u8>) -> String {
-    v.iter().map(|&x| x as char).collect()
+    Bytes(v.clone()).to_latin1()
+}
+
+/// the inverse of `raw_to_string`, so only chars up to U+00FF are allowed.
+pub fn string_to_raw(s: &str) -> Result<Vec<u8>, DecodeError> {
+    Bytes::from_latin1(s).map(|Bytes(v)| v)
+}
+
+pub fn decode_hex(s: &str) -> Result<Hex, DecodeError> {
+    // odd length input is treated as having an implicit leading zero.
+    let chars: Vec<char> = s.chars().collect();
+    let skew = chars.len() % 2;
+    let mut result = Vec::with_capacity((chars.len() + 1) / 2);
+    let mut high = 0;
+    for (i, &c) in chars.iter().enumerate() {
+        let n = try!(hex_to_int(c, i));
+        if (i + skew) % 2 == 0 {
+            high = n;
+        } else {
+            result.push(16 * high + n);
+        }
+    }
+    Ok(Hex(result))
+}
+
+impl Base64Config {
+    pub fn encode(&self, v: &[u8]) -> String {
+        let alphabet = self.alphabet.as_bytes();
+        let mut encoded = String::with_capacity((v.len() + 2) / 3 * 4);
+        for elem in v.chunks(3) {
+            // missing bytes in the final group are treated as zero and padded out.
+            let b1 = elem.get(1).cloned().unwrap_or(0);
+            let b2 = elem.get(2).cloned().unwrap_or(0);
+            let s1 = elem[0] >> 2;
+            let s2 = ((elem[0] & 0x3) << 4) | ((b1 & 0xf0) >> 4);
+            let s3 = ((b1 & 0x0f) << 2) | ((b2 & 0xc0) >> 6);
+            let s4 = b2 & 0x3f;
+            encoded.push(alphabet[s1 as usize] as char);
+            encoded.push(alphabet[s2 as usize] as char);
+            if elem.len() > 1 {
+                encoded.push(alphabet[s3 as usize] as char);
+            }
+            if elem.len() > 2 {
+                encoded.push(alphabet[s4 as usize] as char);
+            }
+            if self.padding != PaddingPolicy::Omitted {
+                for _ in elem.len()..3 {
+                    encoded.push('=');
+                }
+            }
+        }
+
+        match self.line_width {
+            Some(width) if width > 0 => {
+                let lines: Vec<&str> = encoded.as_bytes().chunks(width).map(|line| {
+                    // the encoded alphabet is ascii, so any split is on a char boundary.
+                    str::from_utf8(line).unwrap()
+                }).collect();
+                lines.join(self.line_ending)
+            }
+            _ => encoded
+        }
+    }
+
+    pub fn decode(&self, s: &str) -> Result<Base64, DecodeError> {
+        // keep track of the original offsets so errors point into `s`.
+        let chars: Vec<(usize, char)> = s.chars().enumerate().filter(|&(_, c)| {
+            !(self.ignore_whitespace && c.is_whitespace())
+        }).collect();
+        let end = s.chars().count();
+        let offset_of = |i: usize| chars.get(i).map(|&(n, _)| n).unwrap_or(end);
+
+        let remainder = chars.len() % 4;
+        let unpadded_ok = self.padding != PaddingPolicy::Required;
+        if remainder == 1 || (remainder != 0 && !unpadded_ok) {
+            return Err(DecodeError::TruncatedGroup(offset_of(chars.len() - remainder)));
+        }
+
+        let groups = (chars.len() + 3) / 4;
+        let mut result = Vec::with_capacity(groups * 3);
+        for (g, elem) in chars.chunks(4).enumerate() {
+            let first = g * 4;
+            // padding may only appear at the end of the final group.
+            let padding = elem.iter().rev().take_while(|&&(_, c)| c == '=').count();
+            let length = elem.len() - padding;
+            if let Some(&(n, c)) = elem[..length].iter().find(|&&(_, c)| c == '=') {
+                return Err(DecodeError::InvalidPadding(c, n));
+            }
+            if padding > 0 {
+                let (n, c) = elem[length];
+                if self.padding == PaddingPolicy::Omitted || elem.len() != 4 || length < 2 || g + 1 != groups {
+                    return Err(DecodeError::InvalidPadding(c, n));
+                }
+            }
+            if length == 1 {
+                return Err(DecodeError::TruncatedGroup(offset_of(first)));
+            }
+
+            let mut e = [0u8; 4];
+            for (i, &(n, c)) in elem[..length].iter().enumerate() {
+                e[i] = try!(base64_to_int(self.alphabet, c, n));
+            }
+            result.push((e[0] << 2) | (e[1] >> 4));
+            if length > 2 {
+                result.push((e[1] << 4) | (e[2] >> 2));
+            }
+            if length > 3 {
+                result.push((e[2] << 6) | e[3]);
+            }
+        }
+        Ok(Base64(result))
+    }
+}
+
+impl Base64 {
+    pub fn encode_with(&self, config: &Base64Config) -> String {
+        let Base64(ref v) = *self;
+        config.encode(v)
+    }
+
+    pub fn decode_with(s: &str, config: &Base64Config) -> Result<Base64, DecodeError> {
+        config.decode(s)
+    }
+}
+
+// shared conversions for the byte wrapper types.
+macro_rules! byte_wrapper {
+    ($name:ident) => {
+        impl ops::Deref for $name {
+            type Target = [u8];
+            fn deref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl From<Vec<u8>> for $name {
+            fn from(v: Vec<u8>) -> $name {
+                $name(v)
+            }
+        }
+
+        impl<'a> From<&'a [u8]> for $name {
+            fn from(v: &'a [u8]) -> $name {
+                $name(v.to_vec())
+            }
+        }
+
+        impl From<$name> for Vec<u8> {
+            fn from(v: $name) -> Vec<u8> {
+                v.0
+            }
+        }
+
+        // compare against the encoded form, so hex compares case-insensitively.
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                match other.parse::<$name>() {
+                    Ok(decoded) => decoded.0 == self.0,
+                    Err(_) => false
+                }
+            }
+        }
+
+        impl<'a> PartialEq<&'a str> for $name {
+            fn eq(&self, other: &&'a str) -> bool {
+                *self == **other
+            }
+        }
+    }
+}
+
+byte_wrapper!(Hex);
+byte_wrapper!(Base64);
+byte_wrapper!(Base32);
+byte_wrapper!(Ascii85);
+byte_wrapper!(Percent);
+byte_wrapper!(Bytes);
+
+impl str::FromStr for Hex {
+    type Err = DecodeError;
+    fn from_str(s: &str) -> Result<Hex, DecodeError> {
+        decode_hex(s)
+    }
+}
+
+impl str::FromStr for Base64 {
+    type Err = DecodeError;
+    fn from_str(s: &str) -> Result<Base64, DecodeError> {
+        decode_base64(s)
+    }
+}
+
+impl str::FromStr for Base32 {
+    type Err = DecodeError;
+    fn from_str(s: &str) -> Result<Base32, DecodeError> {
+        decode_base32(s)
+    }
+}
+
+impl str::FromStr for Ascii85 {
+    type Err = DecodeError;
+    fn from_str(s: &str) -> Result<Ascii85, DecodeError> {
+        decode_ascii85(s)
+    }
+}
+
+impl str::FromStr for Percent {
+    type Err = DecodeError;
+    fn from_str(s: &str) -> Result<Percent, DecodeError> {
+        decode_percent(s)
+    }
+}
+
+impl str::FromStr for Bytes {
+    type Err = DecodeError;
+    fn from_str(s: &str) -> Result<Bytes, DecodeError> {
+        Bytes::from_escaped(s)
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", encode_hex(&self.0))
+    }
 }
 
-pub fn string_to_raw(s: &str) -> Vec<u8> {
-    s.chars().map(|c| c as u8).collect()
+impl fmt::LowerHex for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", HEX_LOWER.encode(&self.0))
+    }
+}
+
+impl fmt::UpperHex for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", HEX_UPPER.encode(&self.0))
+    }
+}
+
+impl fmt::Display for Base64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", encode_base64(&self.0))
+    }
+}
+
+impl fmt::Display for Base32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", encode_base32(&self.0))
+    }
+}
+
+impl fmt::Display for Ascii85 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", encode_ascii85(&self.0))
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", encode_percent(&self.0))
+    }
+}
+
+impl From<Hex> for Base64 {
+    fn from(v: Hex) -> Base64 {
+        Base64(v.0)
+    }
+}
+
+impl From<Base64> for Hex {
+    fn from(v: Base64) -> Hex {
+        Hex(v.0)
+    }
+}
+
+impl From<Hex> for Bytes {
+    fn from(v: Hex) -> Bytes {
+        Bytes(v.0)
+    }
+}
+
+impl From<Base64> for Bytes {
+    fn from(v: Base64) -> Bytes {
+        Bytes(v.0)
+    }
+}
+
+pub fn decode_base64(s: &str) -> Result<Base64, DecodeError> {
+    BASE64_STANDARD.decode(s)
 }
 
 pub fn string_to_hex(s: &str) -> Hex {
-    let padded = if (s.len() % 2) == 1 {
-        format!("0{}", s)
-    } else {
-        s.to_string()
-    };
-    let result = padded.as_bytes().chunks(2).map(|elem| {
-        let first = elem[0];
-        let second = elem[1];
-        let n = 16 * hex_to_int(first as char) + hex_to_int(second as char);
-        n as u8
-    }).collect();
-    Hex(result)
+    match decode_hex(s) {
+        Ok(hex) => hex,
+        Err(e) => panic!("{}", e)
+    }
 }
 
 pub fn string_to_base64(s: &str) -> Base64 {
-    assert!((s.len() % 4) == 0);
-    let result = s.as_bytes().chunks(4).flat_map(|elem| {
-        let e0 = BASE64_ALPHABET.find(elem[0] as char).unwrap() as u8;
-        let e1 = BASE64_ALPHABET.find(elem[1] as char).unwrap() as u8;
-        let s1 = (e0 << 2) | (e1 >> 4);
-        if elem[2] != b'=' {
-            let e2 = BASE64_ALPHABET.find(elem[2] as char).unwrap() as u8;
-            let s2 = (e1 << 4) | (e2 >> 2);
-            if elem[3] != b'=' {
-                let e3 = BASE64_ALPHABET.find(elem[3] as char).unwrap() as u8;
-                let s3 = (e2 << 6) | e3;
-                vec![s1, s2, s3]
-            } else {
-                vec![s1, s2]
+    match decode_base64(s) {
+        Ok(base64) => base64,
+        Err(e) => panic!("{}", e)
+    }
+}
+
+impl HexConfig {
+    pub fn encode(&self, v: &[u8]) -> String {
+        let alphabet = match self.case {
+            Case::Lower => HEX_LOWER_ALPHABET.as_bytes(),
+            Case::Upper => HEX_ALPHABET.as_bytes(),
+        };
+        let group_size = if self.group_size == 0 { v.len() } else { self.group_size };
+        let mut result = String::with_capacity(v.len() * (2 + self.separator.len()));
+        for (i, &elem) in v.iter().enumerate() {
+            if i > 0 && i % group_size == 0 {
+                result.push_str(self.separator);
             }
-        } else {
-            vec![s1]
+            result.push(alphabet[(elem >> 4) as usize] as char);
+            result.push(alphabet[(elem & 0xf) as usize] as char);
         }
-    }).collect();
-    Base64(result)
+        result
+    }
+
+    /// accepts either case, with or without separators.
+    pub fn decode(&self, s: &str) -> Result<Hex, DecodeError> {
+        if self.separator.len() == 0 {
+            return decode_hex(s);
+        }
+        let mut result = vec![];
+        let mut start = 0;
+        for group in s.split(self.separator) {
+            if group.len() % 2 == 1 {
+                return Err(DecodeError::TruncatedGroup(start));
+            }
+            let Hex(v) = try!(decode_hex(group).map_err(|e| relocate_error(e, start)));
+            result.extend(v);
+            start += group.chars().count() + self.separator.chars().count();
+        }
+        Ok(Hex(result))
+    }
+}
+
+impl Hex {
+    pub fn encode_with(&self, config: &HexConfig) -> String {
+        config.encode(&self.0)
+    }
+
+    pub fn decode_with(s: &str, config: &HexConfig) -> Result<Hex, DecodeError> {
+        config.decode(s)
+    }
+}
+
+/// lowercase, like the test vectors. use `HEX_UPPER` for uppercase.
+pub fn encode_hex(v: &[u8]) -> String {
+    HEX_LOWER.encode(v)
+}
+
+/// an `xxd`-style dump: offset, sixteen bytes in pairs, and an ascii gutter.
+pub fn hexdump(v: &[u8]) -> String {
+    let width = 16;
+    let mut result = String::new();
+    for (i, line) in v.chunks(width).enumerate() {
+        let hex = HexConfig { case: Case::Lower, separator: " ", group_size: 2 }.encode(line);
+        // pad short lines so the gutter always lines up.
+        let full = width * 2 + (width / 2 - 1);
+        let gutter: String = line.iter().map(|&x| {
+            if x >= 0x20 && x < 0x7f { x as char } else { '.' }
+        }).collect();
+        result.push_str(&format!("{:08x}: {:<3$}  {}\n", i * width, hex, gutter, full));
+    }
+    result
 }
 
 pub fn hex_to_string(raw: Hex) -> String {
-    let Hex(v) = raw;
-    if v.len() == 0 {
-        return "".to_string();
+    encode_hex(&raw)
+}
+
+pub fn encode_base64(v: &[u8]) -> String {
+    BASE64_STANDARD.encode(v)
+}
+
+pub fn raw_to_base64(v: &Vec<u8>) -> Base64 {
+    Base64(v.clone())
+}
+
+pub fn base64_to_string(raw: Base64) -> String {
+    let Base64(v) = raw;
+    encode_base64(&v)
+}
+
+pub fn encode_base32(v: &[u8]) -> String {
+    let alphabet = BASE32_ALPHABET.as_bytes();
+    let mut result = String::with_capacity((v.len() + 4) / 5 * 8);
+    for elem in v.chunks(5) {
+        let mut group = [0u8; 5];
+        group[..elem.len()].copy_from_slice(elem);
+        let bits = group.iter().fold(0u64, |accum, &x| (accum << 8) | x as u64);
+        // only emit the characters that carry input bits.
+        let used = (elem.len() * 8 + 4) / 5;
+        for i in 0..8 {
+            if i < used {
+                let index = (bits >> (35 - 5 * i)) & 0x1f;
+                result.push(alphabet[index as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
     }
-    let result: String = v.iter().map(|&elem| {
-        let mut x: u32 = elem as u32;
-        let mut v = String::new();
-        while x > 0 {
-            v.push(HEX_ALPHABET.char_at((x % 16) as usize));
-            x /= 16;
+    result
+}
+
+/// case-insensitive, and tolerates missing padding as found in TOTP secrets.
+pub fn decode_base32(s: &str) -> Result<Base32, DecodeError> {
+    let chars: Vec<char> = s.chars().collect();
+    let padding = chars.iter().rev().take_while(|&&c| c == '=').count();
+    let length = chars.len() - padding;
+    if let Some(i) = chars[..length].iter().position(|&c| c == '=') {
+        return Err(DecodeError::InvalidPadding('=', i));
+    }
+    let remainder = length % 8;
+    if remainder == 1 || remainder == 3 || remainder == 6 {
+        return Err(DecodeError::TruncatedGroup(length - remainder));
+    }
+    if padding > 0 && (remainder == 0 || remainder + padding != 8) {
+        return Err(DecodeError::InvalidPadding('=', length));
+    }
+
+    let mut result = Vec::with_capacity(length * 5 / 8);
+    let mut bits = 0u64;
+    let mut count = 0;
+    for (i, &c) in chars[..length].iter().enumerate() {
+        let n = match BASE32_ALPHABET.find(c.to_ascii_uppercase()) {
+            Some(n) => n as u64,
+            None => return Err(DecodeError::InvalidCharacter(c, i))
+        };
+        bits = (bits << 5) | n;
+        count += 5;
+        if count >= 8 {
+            count -= 8;
+            result.push((bits >> count) as u8);
+            bits &= (1 << count) - 1;
         }
+    }
+    Ok(Base32(result))
+}
 
-        // pad out hexcodes
-        let original = elem as u32;
-        if original < 16 {
-            v.push('0');
+/// Adobe-style Ascii85, without the `<~` `~>` delimiters.
+pub fn encode_ascii85(v: &[u8]) -> String {
+    let mut result = String::with_capacity((v.len() + 3) / 4 * 5);
+    for elem in v.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..elem.len()].copy_from_slice(elem);
+        let mut n = group.iter().fold(0u32, |accum, &x| (accum << 8) | x as u32);
+        if n == 0 && elem.len() == 4 {
+            result.push('z');
+            continue;
         }
-        if original == 0 {
-            v.push('0');
+        let mut digits = [0u8; 5];
+        for i in (0..5).rev() {
+            digits[i] = (n % 85) as u8 + b'!';
+            n /= 85;
         }
+        // a partial group of n bytes is written as n + 1 characters.
+        for &d in digits[..elem.len() + 1].iter() {
+            result.push(d as char);
+        }
+    }
+    result
+}
 
-        v.chars().rev().collect::<String>()
+/// accepts optional `<~` `~>` delimiters and ignores whitespace.
+pub fn decode_ascii85(s: &str) -> Result<Ascii85, DecodeError> {
+    let mut chars: Vec<(usize, char)> = s.chars().enumerate().filter(|&(_, c)| {
+        !c.is_whitespace()
     }).collect();
+    if chars.len() >= 2 && chars[0].1 == '<' && chars[1].1 == '~' {
+        chars.drain(..2);
+    }
+    let n = chars.len();
+    if n >= 2 && chars[n - 2].1 == '~' && chars[n - 1].1 == '>' {
+        chars.truncate(n - 2);
+    }
+
+    let mut result = Vec::with_capacity(chars.len() * 4 / 5);
+    let mut group: Vec<(usize, char)> = Vec::with_capacity(5);
+    for &(i, c) in chars.iter() {
+        if c == 'z' && group.len() == 0 {
+            result.extend_from_slice(&[0, 0, 0, 0]);
+            continue;
+        }
+        if c < '!' || c > 'u' {
+            return Err(DecodeError::InvalidCharacter(c, i));
+        }
+        group.push((i, c));
+        if group.len() == 5 {
+            let bytes = try!(ascii85_group(&group));
+            result.extend_from_slice(&bytes);
+            group.clear();
+        }
+    }
+    match group.len() {
+        0 => (),
+        1 => return Err(DecodeError::TruncatedGroup(group[0].0)),
+        length => {
+            // pad with the highest digit and keep one byte less than we read.
+            let last = group[length - 1].0;
+            while group.len() < 5 {
+                group.push((last, 'u'));
+            }
+            let bytes = try!(ascii85_group(&group));
+            result.extend_from_slice(&bytes[..length - 1]);
+        }
+    }
+    Ok(Ascii85(result))
+}
+
+fn ascii85_group(group: &[(usize, char)]) -> Result<[u8; 4], DecodeError> {
+    let mut n = 0u64;
+    for &(_, c) in group {
+        n = n * 85 + (c as u64 - '!' as u64);
+    }
+    if n > 0xffffffff {
+        let (i, c) = group[group.len() - 1];
+        return Err(DecodeError::InvalidCharacter(c, i));
+    }
+    Ok([(n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8])
+}
+
+/// percent encode everything but the unreserved characters.
+pub fn encode_percent(v: &[u8]) -> String {
+    let mut result = String::with_capacity(v.len());
+    for &x in v {
+        if x < 0x80 && URL_UNRESERVED.contains(x as char) {
+            result.push(x as char);
+        } else {
+            result.push_str(&format!("%{:02X}", x));
+        }
+    }
     result
 }
 
-pub fn raw_to_base64(v: &Vec<u8>) -> Base64 {
-    let result = v.chunks(3).flat_map(|elem| {
-        let s1 = elem[0] >> 2;
-        let s2 = ((elem[0] & 0x3) << 4) | ((elem[1] & 0xf0) >> 4);
-        let s3 = ((elem[1] & 0x0f) << 2) | ((elem[2] & 0xc0) >> 6);
-        let s4 = elem[2] & 0x3f;
-        vec![s1, s2, s3, s4]
-    }).collect();
-    Base64(result)
+/// any character other than a `%` escape stands for its own utf-8 bytes.
+pub fn decode_percent(s: &str) -> Result<Percent, DecodeError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut result = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '%' {
+            if i + 2 >= chars.len() {
+                return Err(DecodeError::TruncatedGroup(i));
+            }
+            let high = try!(hex_to_int(chars[i + 1], i + 1));
+            let low = try!(hex_to_int(chars[i + 2], i + 2));
+            result.push(16 * high + low);
+            i += 3;
+        } else {
+            let mut buffer = [0; 4];
+            result.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            i += 1;
+        }
+    }
+    Ok(Percent(result))
 }
 
-pub fn base64_to_string(raw: Base64) -> String {
-    let Base64(v) = raw;
-    if v.len() == 0 {
-        return "".to_string();
+pub fn try_read_base64_file_with(filename: &str, config: &Base64Config) -> Result<Base64, ReadError> {
+    let f = try!(File::open(filename));
+    // files are always line-wrapped, whatever the codec.
+    let config = Base64Config { ignore_whitespace: true, .. *config };
+    let mut reader = Base64Reader::with_config(f, &config);
+    let mut result = vec![];
+    try!(reader.read_to_end(&mut result));
+    Ok(Base64(result))
+}
+
+pub fn try_read_base64_file(filename: &str) -> Result<Base64, ReadError> {
+    try_read_base64_file_with(filename, &BASE64_STANDARD)
+}
+
+pub fn try_read_hexlines_file(filename: &str) -> Result<Vec<Vec<u8>>, ReadError> {
+    let mut f = BufReader::new(try!(File::open(filename)));
+    let mut result = vec![];
+    let mut start = 0;
+    let mut line = vec![];
+    loop {
+        line.clear();
+        let n = try!(f.read_until(b'\n', &mut line));
+        if n == 0 {
+            // a trailing newline does not start another record.
+            break;
+        }
+        while line.last() == Some(&b'\n') || line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        // report offsets relative to the whole file, not the line.
+        let relocate = |e| relocate_error(e, start);
+        let text = try!(str::from_utf8(&line).map_err(|e| {
+            relocate(DecodeError::InvalidCharacter('\u{fffd}', e.valid_up_to()))
+        }));
+        let Hex(v) = try!(decode_hex(text).map_err(relocate));
+        result.push(v);
+        start += n;
     }
-    v.iter().map(|&elem| {
-        let mut x: u32 = elem as u32;
-        let mut v = String::new();
-        while x > 0 {
-            v.push(BASE64_ALPHABET.char_at((x % 64) as usize));
-            x /= 64;
+    Ok(result)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Hex,
+    Base64,
+    Base64UrlSafe,
+    Binary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// the whole file is one record, possibly wrapped over several lines.
+    Whole,
+    /// each line is a separate record.
+    Lines,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileFormat {
+    pub encoding: Encoding,
+    pub layout: Layout,
+}
+
+fn relocate_error(e: DecodeError, start: usize) -> DecodeError {
+    match e {
+        DecodeError::InvalidCharacter(c, n) => DecodeError::InvalidCharacter(c, start + n),
+        DecodeError::InvalidPadding(c, n) => DecodeError::InvalidPadding(c, start + n),
+        DecodeError::TruncatedGroup(n) => DecodeError::TruncatedGroup(start + n),
+    }
+}
+
+// split into lines with surrounding whitespace trimmed, along with where
+// each trimmed line starts. the final newline doesn't start another line.
+fn text_lines(s: &str) -> Vec<(usize, &str)> {
+    let mut result = vec![];
+    let mut start = 0;
+    for line in s.split('\n') {
+        let trimmed = line.trim_left();
+        result.push((start + line.len() - trimmed.len(), trimmed.trim_right()));
+        start += line.len() + 1;
+    }
+    if s.ends_with('\n') {
+        result.pop();
+    }
+    result
+}
+
+pub fn detect_format(data: &[u8]) -> FileFormat {
+    let binary = FileFormat { encoding: Encoding::Binary, layout: Layout::Whole };
+    let text = match str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return binary
+    };
+    if !text.chars().all(|c| c.is_whitespace() || (c >= ' ' && c <= '~')) {
+        return binary;
+    }
+    let lines = text_lines(text);
+    let records: Vec<&str> = lines.iter().map(|&(_, l)| l).collect();
+    if records.iter().all(|l| l.is_empty()) {
+        return binary;
+    }
+
+    // hex files in practice are one record per line. odd lengths are fine,
+    // as `decode_hex` takes them to have a leading zero.
+    let is_hex = records.iter().all(|l| l.chars().all(|c| c.is_digit(16)));
+    if is_hex {
+        let layout = if records.len() > 1 { Layout::Lines } else { Layout::Whole };
+        return FileFormat { encoding: Encoding::Hex, layout: layout };
+    }
+
+    let standard = text.chars().any(|c| c == '+' || c == '/');
+    let url_safe = text.chars().any(|c| c == '-' || c == '_');
+    let is_base64 = records.iter().all(|l| {
+        l.chars().all(|c| c.is_alphanumeric() || "+/-_=".contains(c))
+    });
+    if !is_base64 || (standard && url_safe) {
+        return binary;
+    }
+    let encoding = if url_safe { Encoding::Base64UrlSafe } else { Encoding::Base64 };
+
+    // a wrapped blob has equal line lengths, a multiple of four, and padding
+    // only on the last line. anything else is a record per line.
+    let (last, rest) = records.split_last().unwrap();
+    let width = records[0].len();
+    let wrapped = rest.iter().all(|l| l.len() == width && !l.contains('=')) &&
+        width % 4 == 0 && last.len() <= width;
+    let layout = if wrapped { Layout::Whole } else { Layout::Lines };
+    FileFormat { encoding: encoding, layout: layout }
+}
+
+pub fn decode_records(data: &[u8], format: &FileFormat) -> Result<Vec<Vec<u8>>, DecodeError> {
+    let alphabet = match format.encoding {
+        Encoding::Binary => return Ok(vec![data.to_vec()]),
+        Encoding::Base64UrlSafe => BASE64_URL_SAFE_ALPHABET,
+        _ => BASE64_ALPHABET,
+    };
+    let config = Base64Config {
+        alphabet: alphabet,
+        padding: PaddingPolicy::Optional,
+        .. BASE64_STANDARD
+    };
+    let text = match str::from_utf8(data) {
+        Ok(text) => text,
+        Err(e) => return Err(DecodeError::InvalidCharacter('\u{fffd}', e.valid_up_to()))
+    };
+    let decode = |s: &str| match format.encoding {
+        Encoding::Hex => decode_hex(s).map(|Hex(v)| v),
+        _ => config.decode(s).map(|Base64(v)| v)
+    };
+
+    match format.layout {
+        Layout::Whole => {
+            let config = Base64Config { ignore_whitespace: true, .. config };
+            match format.encoding {
+                Encoding::Hex => {
+                    let trimmed = text.trim_left();
+                    let start = text.len() - trimmed.len();
+                    decode(trimmed.trim_right()).map(|v| vec![v]).map_err(|e| relocate_error(e, start))
+                }
+                _ => config.decode(text).map(|Base64(v)| vec![v])
+            }
         }
-        v.chars().rev().collect::<String>()
-    }).collect()
+        Layout::Lines => {
+            let mut result = vec![];
+            for (start, line) in text_lines(text) {
+                result.push(try!(decode(line).map_err(|e| relocate_error(e, start))));
+            }
+            Ok(result)
+        }
+    }
+}
+
+pub fn try_load_file(filename: &str) -> Result<(FileFormat, Vec<Vec<u8>>), ReadError> {
+    let mut f = try!(File::open(filename));
+    let mut data = vec![];
+    try!(f.read_to_end(&mut data));
+    let format = detect_format(&data);
+    let records = try!(decode_records(&data, &format));
+    Ok((format, records))
+}
+
+pub fn load_file(filename: &str) -> (FileFormat, Vec<Vec<u8>>) {
+    match try_load_file(filename) {
+        Ok(result) => result,
+        Err(e) => panic!("{}: {}", filename, e)
+    }
 }
 
 pub fn read_base64_file(filename: &str) -> Base64 {
-    let mut f = File::open(filename).unwrap();
-    let mut s = String::new();
-    let _ = f.read_to_string(&mut s);
-    let raw: String = s.split('\n').flat_map(|x| x.chars()).collect();
-    string_to_base64(&raw)
+    match try_read_base64_file(filename) {
+        Ok(base64) => base64,
+        Err(e) => panic!("{}: {}", filename, e)
+    }
 }
 
 pub fn read_hexlines_file(filename: &str) -> Vec<Vec<u8>> {
-    let mut f = File::open(filename).unwrap();
-    let mut s = String::new();
-    let _ = f.read_to_string(&mut s);
-    s.split('\n').map(|l| {
-        let Hex(v) = string_to_hex(&l);
-        v
-    }).collect()
+    match try_read_hexlines_file(filename) {
+        Ok(lines) => lines,
+        Err(e) => panic!("{}: {}", filename, e)
+    }
 }
@@ -1,11 +1,15 @@
 
-use std::{iter, ops};
+use std::{cmp, iter, ops};
 use std::collections::{HashMap, BTreeMap};
-use openssl::crypto::symm::{Crypter, Mode, Type};
 use rand::distributions::{IndependentSample, Range};
 use rand::{thread_rng, Rng};
 
+use cipher::*;
 use conversion::*;
+use form::*;
+use keysize::*;
+use padding::*;
+use score::*;
 use util::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
@@ -14,141 +18,428 @@ pub enum EncryptionMode {
     CBC
 }
 
-pub fn single_byte_xor(v: &Vec<u8>) -> BTreeMap<i64, String> {
-    let keys = ascii_single_keys();
-    let mut map = BTreeMap::<i64, String>::new();
-    for i in keys {
-        let result = raw_to_string(&xor_one(&v, i as u8));
-        let p = english_probability(&result);
-        map.insert(-p, result.clone()); // flip so that better results come first
-    }
-    map
+/// a candidate decryption under a single byte key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub key: u8,
+    pub score: f64,
+    pub plaintext: Vec<u8>,
+    /// which of the inputs was decrypted.
+    pub index: usize,
 }
 
-pub fn detect_single_byte_xor(tests: &Vec<Vec<u8>>) -> BTreeMap<i64, String> {
-    let keys = ascii_single_keys();
-    let mut map = BTreeMap::new();
+// best first; equal scores keep their original order.
+fn rank(candidates: &mut Vec<Candidate>, limit: usize) {
+    candidates.sort_by(|a, b| best_first(a.score, b.score));
+    candidates.truncate(limit);
+}
 
-    for test in tests {
-        for i in keys.clone() {
-            let result = raw_to_string(&xor_one(&test, i as u8));
-            let p = english_probability(&result);
-            map.insert(-p, result.clone()); // flip so that better results come first
+/// tries every key byte and returns the `limit` most plausible results.
+pub fn single_byte_xor(v: &Vec<u8>, scorer: &Scorer, limit: usize) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = (0..256).map(|key| {
+        let plaintext = xor_one(&v, key as u8);
+        Candidate {
+            key: key as u8,
+            score: scorer.score(&plaintext),
+            plaintext: plaintext,
+            index: 0,
+        }
+    }).collect();
+    rank(&mut candidates, limit);
+    candidates
+}
+
+/// finds which of `tests` is most likely single byte xor'd text.
+pub fn detect_single_byte_xor(tests: &Vec<Vec<u8>>, scorer: &Scorer, limit: usize) -> Vec<Candidate> {
+    let mut candidates = vec![];
+    for (index, test) in tests.iter().enumerate() {
+        for mut candidate in single_byte_xor(test, scorer, limit) {
+            candidate.index = index;
+            candidates.push(candidate);
         }
     }
-    map
+    rank(&mut candidates, limit);
+    candidates
 }
 
+/// candidate key lengths, most likely first. see `keysize` for other methods.
 pub fn likely_keysizes(block: &Vec<u8>, range: ops::Range<usize>) -> Vec<usize> {
+    hamming_keysizes(block, range).iter().map(|k| k.length).collect()
+}
 
-    let mut map = BTreeMap::new();
+// the best single byte key for each column of a `length` byte key.
+fn repeating_key(block: &Vec<u8>, length: usize, scorer: &Scorer) -> Vec<u8> {
+    // transpose the blocks so we can xor each block with an individual key
+    let chunks: Vec<Vec<u8>> = block.chunks(length).map(|c| {
+        c.iter().cloned().collect()
+    }).collect();
+    let transposed = transpose(&chunks);
 
-    for keysize in range {
-        // take four keysize blocks ...
-        let first: &Vec<u8> = &block[..keysize].to_vec();
-        let second: &Vec<u8> = &block[keysize..keysize*2].to_vec();
-        let third: &Vec<u8> = &block[keysize*2..keysize*3].to_vec();
-        let fourth: &Vec<u8> = &block[keysize*3..keysize*4].to_vec();
+    // get the best matching single key per block
+    transposed.iter().map(|column| single_byte_xor(column, scorer, 1)[0].key).collect()
+}
 
-        // ... get the hamming distance for each ...
-        let edit_dist1 = hamming(first, second) as f64 / keysize as f64;
-        let edit_dist2 = hamming(first, third) as f64 / keysize as f64;
-        let edit_dist3 = hamming(first, fourth) as f64 / keysize as f64;
-        let edit_dist4 = hamming(second, third) as f64 / keysize as f64;
-        let edit_dist5 = hamming(second, fourth) as f64 / keysize as f64;
-        let edit_dist6 = hamming(third, fourth) as f64 / keysize as f64;
+pub fn break_repeating_key_xor(block: &Vec<u8>, lengths: &Vec<usize>, scorer: &Scorer) -> Vec<String> {
+    lengths.iter().map(|&length| raw_to_string(&repeating_key(block, length, scorer))).collect()
+}
 
-        // ... and average the distances.
-        let norm = (edit_dist1 + edit_dist2 + edit_dist3 +
-                    edit_dist4 + edit_dist5 + edit_dist6) / 6.0 * 100000.0;
+/// a candidate repeating key and what it decrypts to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepeatingKeySolution {
+    pub key: Vec<u8>,
+    pub plaintext: Vec<u8>,
+    /// the scorer's rating of the plaintext, our confidence in the key.
+    pub score: f64,
+}
 
-        map.insert(norm as usize, keysize);
+/// breaks repeating key xor end to end: estimates key lengths, recovers a key
+/// for the `tries` most likely of them and returns every distinct key found,
+/// best first. keys that repeat a shorter key are reduced to it.
+pub fn solve_repeating_key_xor(block: &Vec<u8>, range: ops::Range<usize>, tries: usize,
+                               scorer: &Scorer) -> Vec<RepeatingKeySolution> {
+    // the estimators fail in different ways, so try the best of two.
+    let mut lengths: Vec<usize> = vec![];
+    let hamming = hamming_keysizes(block, range.clone());
+    let coincidence = coincidence_keysizes(block, range);
+    for k in hamming.iter().take(tries).chain(coincidence.iter().take(tries)) {
+        if !lengths.contains(&k.length) {
+            lengths.push(k.length);
+        }
     }
 
-    map.values().cloned().collect()
+    let mut solutions: Vec<RepeatingKeySolution> = vec![];
+    for length in lengths {
+        let key = repeating_key(block, length, scorer);
+        let key = shortest_period(&key).to_vec();
+        if solutions.iter().any(|s| s.key == key) {
+            continue;
+        }
+        let plaintext = xor_key_bytes(block, &key);
+        solutions.push(RepeatingKeySolution {
+            key: key,
+            score: scorer.score(&plaintext),
+            plaintext: plaintext,
+        });
+    }
+    solutions.sort_by(|a, b| best_first(a.score, b.score));
+    solutions
 }
 
-pub fn break_repeating_key_xor(block: &Vec<u8>, lengths: &Vec<usize>) -> Vec<String> {
+/// any trailing partial block is dropped, so pad first.
+pub fn ecb_encrypt<C: BlockCipher + ?Sized>(cipher: &C, v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    let mut result = v[..v.len() - v.len() % n].to_vec();
+    for block in result.chunks_mut(n) {
+        cipher.encrypt_block(block);
+    }
+    result
+}
 
-    let mut solutions = vec![];
-    let keys = ascii_single_keys();
+/// any trailing partial block is dropped.
+pub fn ecb_decrypt<C: BlockCipher + ?Sized>(cipher: &C, v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    let mut result = v[..v.len() - v.len() % n].to_vec();
+    for block in result.chunks_mut(n) {
+        cipher.decrypt_block(block);
+    }
+    result
+}
+
+/// any trailing partial block is dropped, so pad first.
+pub fn cbc_encrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    assert!(iv.len() == n);
+    let mut result = v[..v.len() - v.len() % n].to_vec();
+    let mut previous = iv.to_vec();
+    for block in result.chunks_mut(n) {
+        xor_into(block, &previous);
+        cipher.encrypt_block(block);
+        previous.copy_from_slice(block);
+    }
+    result
+}
 
-    for &length in lengths {
+/// any trailing partial block is dropped.
+pub fn cbc_decrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    assert!(iv.len() == n);
+    let mut result = v[..v.len() - v.len() % n].to_vec();
+    let mut previous = iv.to_vec();
+    for block in result.chunks_mut(n) {
+        let ciphertext = block.to_vec();
+        cipher.decrypt_block(block);
+        xor_into(block, &previous);
+        previous = ciphertext;
+    }
+    result
+}
 
-        // transpose the blocks so we can xor each block with an individual key
-        let chunks: Vec<Vec<u8>> = block.chunks(length).map(|c| {
-            c.iter().cloned().collect()
-        }).collect();
-        let transposed = transpose(&chunks);
+/// full-block cipher feedback. a trailing partial block uses the start of
+/// its keystream block, so nothing is dropped.
+pub fn cfb_encrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    assert!(iv.len() == n);
+    let mut result = v.to_vec();
+    let mut feedback = iv.to_vec();
+    for block in result.chunks_mut(n) {
+        cipher.encrypt_block(&mut feedback);
+        let length = block.len();
+        xor_into(block, &feedback[..length]);
+        feedback[..length].copy_from_slice(block);
+    }
+    result
+}
 
-        // get the best matching single key per block
-        let mut block_key = vec![];
-        for block in transposed {
-            let mut map = BTreeMap::new();
-            for key in keys.clone() {
-                let result = raw_to_string(&xor_one(&block, key as u8));
-                let p = english_probability(&result);
-                map.insert(-p, key);
-            }
-            let best_match = map.iter().next().unwrap();
-            block_key.push(*best_match.1);
-        }
-        let s: String = block_key.iter().map(|&x| x as char).collect();
-        solutions.push(s);
+pub fn cfb_decrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    assert!(iv.len() == n);
+    let mut result = v.to_vec();
+    let mut feedback = iv.to_vec();
+    for block in result.chunks_mut(n) {
+        cipher.encrypt_block(&mut feedback);
+        let length = block.len();
+        let ciphertext = block.to_vec();
+        xor_into(block, &feedback[..length]);
+        feedback[..length].copy_from_slice(&ciphertext);
     }
+    result
+}
 
-    solutions
+// one block cipher call per byte, shifting each ciphertext byte into the register.
+fn cfb8<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8], encrypt: bool) -> Vec<u8> {
+    assert!(iv.len() == cipher.block_size());
+    let mut register = iv.to_vec();
+    let mut result = Vec::with_capacity(v.len());
+    for &x in v {
+        let mut keystream = register.clone();
+        cipher.encrypt_block(&mut keystream);
+        let y = x ^ keystream[0];
+        register.remove(0);
+        register.push(if encrypt { y } else { x });
+        result.push(y);
+    }
+    result
+}
 
+/// cipher feedback with 8-bit segments.
+pub fn cfb8_encrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    cfb8(cipher, iv, v, true)
 }
-pub fn decrypt_aes_ecb(v: &Vec<u8>, key: &str) -> Vec<u8> {
-    let k = string_to_raw(key);
-    let decrypter = Crypter::new(Type::AES_128_ECB);
-    let iv: Vec<u8> = iter::repeat(0).take(16).collect();
-    decrypter.init(Mode::Decrypt, &k, &iv);
-    decrypter.pad(false);
-    decrypter.update(v)
+
+pub fn cfb8_decrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    cfb8(cipher, iv, v, false)
 }
 
-pub fn encrypt_aes_ecb(v: &Vec<u8>, key: &str) -> Vec<u8> {
-    let k = string_to_raw(key);
-    let encrypter = Crypter::new(Type::AES_128_ECB);
-    let iv: Vec<u8> = iter::repeat(0).take(16).collect();
-    encrypter.init(Mode::Encrypt, &k, &iv);
-    encrypter.pad(false);
-    encrypter.update(v)
+/// output feedback. encryption and decryption are the same, and a trailing
+/// partial block is kept.
+pub fn ofb<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    assert!(iv.len() == n);
+    let mut result = v.to_vec();
+    let mut feedback = iv.to_vec();
+    for block in result.chunks_mut(n) {
+        cipher.encrypt_block(&mut feedback);
+        let length = block.len();
+        xor_into(block, &feedback[..length]);
+    }
+    result
 }
 
-pub fn decrypt_aes_cbc(iv: &Vec<u8>, block: &Vec<u8>, key: &str) -> Vec<u8> {
-    let mut previous = iv.clone();
-    let mut result: Vec<u8> = vec![];
-    let chunks: Vec<Vec<u8>> = block.chunks(key.len()).map(|c| {
-        c.iter().cloned().collect()
-    }).collect();
-    for ciphertext in chunks {
-        let block = decrypt_aes_ecb(&ciphertext, key);
-        let mut plaintext = xor(&previous, &block);
-        result.append(&mut plaintext);
-        let copy = &mut ciphertext.clone();
-        previous = copy.clone();
+/// propagating CBC: each block is also xor'd with the previous plaintext, so
+/// a change anywhere garbles everything after it. any trailing partial block
+/// is dropped, so pad first.
+pub fn pcbc_encrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    assert!(iv.len() == n);
+    let mut result = v[..v.len() - v.len() % n].to_vec();
+    let mut previous = iv.to_vec();
+    for block in result.chunks_mut(n) {
+        let plaintext = block.to_vec();
+        xor_into(block, &previous);
+        cipher.encrypt_block(block);
+        previous = xor(&plaintext, block);
     }
     result
 }
 
-pub fn encrypt_aes_cbc(iv: &Vec<u8>, block: &Vec<u8>, key: &str) -> Vec<u8> {
-    let mut previous = iv.clone();
-    let mut result: Vec<u8> = vec![];
-    let chunks: Vec<Vec<u8>> = block.chunks(key.len()).map(|c| {
-        c.iter().cloned().collect()
-    }).collect();
-    for plaintext in chunks {
-        let block = xor(&previous, &plaintext);
-        let mut ciphertext = encrypt_aes_ecb(&block, key);
-        previous = ciphertext.clone();
-        result.append(&mut ciphertext);
+/// any trailing partial block is dropped.
+pub fn pcbc_decrypt<C: BlockCipher + ?Sized>(cipher: &C, iv: &[u8], v: &[u8]) -> Vec<u8> {
+    let n = cipher.block_size();
+    assert!(iv.len() == n);
+    let mut result = v[..v.len() - v.len() % n].to_vec();
+    let mut previous = iv.to_vec();
+    for block in result.chunks_mut(n) {
+        let ciphertext = block.to_vec();
+        cipher.decrypt_block(block);
+        xor_into(block, &previous);
+        previous = xor(&ciphertext, block);
     }
     result
 }
 
+pub fn decrypt_aes_ecb(v: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_cipher(key));
+    Ok(ecb_decrypt(&*cipher, v))
+}
+
+pub fn encrypt_aes_ecb(v: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_cipher(key));
+    Ok(ecb_encrypt(&*cipher, v))
+}
+
+// the cipher for `key`, once `iv` is known to be a single block.
+fn aes_with_iv(iv: &[u8], key: &[u8]) -> Result<Box<BlockCipher>, CipherError> {
+    let cipher = try!(aes_cipher(key));
+    if iv.len() != cipher.block_size() {
+        return Err(CipherError::InvalidIvLength(iv.len()));
+    }
+    Ok(cipher)
+}
+
+pub fn decrypt_aes_cbc(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(cbc_decrypt(&*cipher, iv, block))
+}
+
+pub fn encrypt_aes_cbc(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(cbc_encrypt(&*cipher, iv, block))
+}
+
+pub fn encrypt_aes_cfb(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(cfb_encrypt(&*cipher, iv, block))
+}
+
+pub fn decrypt_aes_cfb(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(cfb_decrypt(&*cipher, iv, block))
+}
+
+pub fn encrypt_aes_cfb8(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(cfb8_encrypt(&*cipher, iv, block))
+}
+
+pub fn decrypt_aes_cfb8(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(cfb8_decrypt(&*cipher, iv, block))
+}
+
+pub fn aes_ofb(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(ofb(&*cipher, iv, block))
+}
+
+pub fn encrypt_aes_pcbc(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(pcbc_encrypt(&*cipher, iv, block))
+}
+
+pub fn decrypt_aes_pcbc(iv: &Vec<u8>, block: &Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_with_iv(iv, key));
+    Ok(pcbc_decrypt(&*cipher, iv, block))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// the layout of a CTR counter block: a nonce followed by a counter, which
+/// together fill one cipher block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CtrLayout {
+    pub nonce_size: usize,
+    /// at most eight bytes. the counter wraps around within this width.
+    pub counter_size: usize,
+    pub endian: Endian,
+}
+
+/// a 64-bit nonce and a little endian 64-bit block count, as in cryptopals.
+pub const CTR_CRYPTOPALS: CtrLayout = CtrLayout {
+    nonce_size: 8,
+    counter_size: 8,
+    endian: Endian::Little,
+};
+
+/// a 96-bit nonce and a big endian 32-bit counter, as in NIST SP 800-38A and GCM.
+pub const CTR_NIST: CtrLayout = CtrLayout {
+    nonce_size: 12,
+    counter_size: 4,
+    endian: Endian::Big,
+};
+
+/// counter mode keystream. encryption and decryption are the same operation.
+///
+/// the keystream can be read from any byte offset: block `n` of the keystream
+/// is the encrypted counter block for counter value `n`.
+pub struct Ctr<C> {
+    cipher: C,
+    layout: CtrLayout,
+    nonce: Vec<u8>,
+    position: u64,
+}
+
+impl<C: BlockCipher> Ctr<C> {
+    pub fn new(cipher: C, layout: &CtrLayout, nonce: &[u8]) -> Result<Ctr<C>, CipherError> {
+        if layout.counter_size > 8 || layout.nonce_size + layout.counter_size != cipher.block_size() {
+            return Err(CipherError::InvalidLayout);
+        }
+        if nonce.len() != layout.nonce_size {
+            return Err(CipherError::InvalidIvLength(nonce.len()));
+        }
+        Ok(Ctr { cipher: cipher, layout: *layout, nonce: nonce.to_vec(), position: 0 })
+    }
+
+    /// the keystream offset, in bytes.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn seek(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    /// xors the keystream into `data` in place, starting at the current
+    /// position, and moves past it. the position wraps around to zero after
+    /// `u64::MAX`.
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        let n = self.cipher.block_size();
+        let mut i = 0;
+        while i < data.len() {
+            let block = self.keystream_block(self.position / n as u64);
+            let offset = (self.position % n as u64) as usize;
+            let length = cmp::min(data.len() - i, n - offset);
+            xor_into(&mut data[i..i + length], &block[offset..offset + length]);
+            i += length;
+            self.position = self.position.wrapping_add(length as u64);
+        }
+    }
+
+    fn keystream_block(&self, counter: u64) -> Vec<u8> {
+        let width = self.layout.counter_size;
+        let mut bytes: Vec<u8> = (0..width).map(|i| (counter >> (8 * i)) as u8).collect();
+        if self.layout.endian == Endian::Big {
+            bytes.reverse();
+        }
+        let mut block = self.nonce.clone();
+        block.extend(bytes);
+        self.cipher.encrypt_block(&mut block);
+        block
+    }
+}
+
+pub fn aes_ctr(layout: &CtrLayout, nonce: &[u8], v: &Vec<u8>,
+               key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = try!(aes_cipher(key));
+    let mut ctr = try!(Ctr::new(cipher, layout, nonce));
+    let mut result = v.clone();
+    ctr.apply_keystream(&mut result);
+    Ok(result)
+}
+
 pub fn test_for_aes_ecb(tests: &Vec<Vec<u8>>) -> Option<Vec<u8>> {
 
     let mut highest = 2; // err on the side of caution
@@ -196,13 +487,14 @@ pub fn encryption_oracle(input: &Vec<u8>) -> (EncryptionMode, Vec<u8>) {
     result.extend(input.clone());
     result.extend(suffix);
 
-    // choose a mode to encrypt
-    let key = raw_to_string(&random_aes());
+    // choose a mode to encrypt. the key and IV are always a block long, so
+    // there's no length to get wrong.
+    let cipher = Aes128::new(&random_aes());
     if rng.gen() {
-        (EncryptionMode::ECB, encrypt_aes_ecb(&result, &key))
+        (EncryptionMode::ECB, ecb_encrypt(&cipher, &result))
     } else {
         let iv = random_aes();
-        (EncryptionMode::CBC, encrypt_aes_cbc(&iv, &result, &key))
+        (EncryptionMode::CBC, cbc_encrypt(&cipher, &iv, &result))
     }
 }
 
@@ -221,18 +513,17 @@ pub fn guess_mode(v: &Vec<u8>) -> EncryptionMode {
 
 pub type Oracle = Fn(&Vec<u8>) -> Vec<u8>;
 
-pub fn create_simple_oracle(_mystery: &Vec<u8>, _key: &str) -> Box<Oracle> {
+pub fn create_simple_oracle<C: BlockCipher + 'static>(_mystery: &Vec<u8>, cipher: C) -> Box<Oracle> {
     let mystery = _mystery.clone();
-    let key = _key.to_string().clone();
     Box::new(move |input: &Vec<u8>| {
         let mut result: Vec<u8> = vec![];
         result.extend(input.clone());
         result.extend(mystery.clone());
-        encrypt_aes_ecb(&result, &key)
+        ecb_encrypt(&cipher, &result)
     })
 }
 
-pub fn decrypt_ecb(oracle: Box<Oracle>) -> String {
+pub fn decrypt_ecb(oracle: Box<Oracle>) -> Vec<u8> {
 
     let x = iter::repeat('x' as u8);
 
@@ -311,75 +602,72 @@ pub fn decrypt_ecb(oracle: Box<Oracle>) -> String {
         }
     }
 
-    return raw_to_string(&decrypted);
+    decrypted
 }
 
-pub fn encrypt_profile(profile: &str) -> (String, Vec<u8>) {
-    let key = raw_to_string(&random_aes());
-    let data = string_to_raw(profile.clone());
-    let encrypted = encrypt_aes_ecb(&data, &key);
+pub fn encrypt_profile(profile: &str) -> (Vec<u8>, Vec<u8>) {
+    let key = random_aes();
+    let data = Pkcs7.pad(profile.as_bytes(), 16);
+    let encrypted = ecb_encrypt(&Aes128::new(&key), &data);
     // "provide" the key to the "attacker"
     (key, encrypted)
 }
 
-pub fn decrypt_profile(profile: &Vec<u8>, key: &str) -> Vec<(String, String)> {
-    let result = decrypt_aes_ecb(&profile, &key);
-    let profile = raw_to_string(&result);
-    match strip_padding(&profile) {
-        Some(stripped) => key_value(&stripped),
-        None => key_value(&profile)
-    }
+/// encrypts the profile for the email address it's given, as a web app would
+/// when setting a cookie.
+pub fn create_profile_oracle<C: BlockCipher + 'static>(cipher: C) -> Box<Oracle> {
+    Box::new(move |email: &Vec<u8>| {
+        let profile = profile_for(&raw_to_string(email));
+        ecb_encrypt(&cipher, &Pkcs7.pad(profile.as_bytes(), cipher.block_size()))
+    })
 }
 
-pub fn create_harder_oracle(_mystery: &Vec<u8>, _key: &str) -> Box<Oracle> {
+pub fn decrypt_profile<C: BlockCipher + ?Sized>(profile: &Vec<u8>, cipher: &C) -> Result<Form, DecodeError> {
+    let result = ecb_decrypt(cipher, &profile);
+    let stripped = match Pkcs7.unpad(&result, cipher.block_size()) {
+        Ok(stripped) => stripped.to_vec(),
+        Err(_) => result
+    };
+    Form::parse_urlencoded(&raw_to_string(&stripped))
+}
+
+pub fn create_harder_oracle<C: BlockCipher + 'static>(_mystery: &Vec<u8>, cipher: C) -> Box<Oracle> {
     let mut rng = thread_rng();
     // append 5-10 bytes before
     let between = Range::new(5, 11);
     let prefix_length = between.ind_sample(&mut rng);
     let prefix: Vec<u8> = (0..).take(prefix_length).map(|_| rng.gen::<u8>()).collect();
     let mystery = _mystery.clone();
-    let key = _key.to_string().clone();
     Box::new(move |input: &Vec<u8>| {
         let mut result: Vec<u8> = vec![];
         result.extend(prefix.clone());
         result.extend(input.clone());
         result.extend(mystery.clone());
-        encrypt_aes_ecb(&result, &key)
+        ecb_encrypt(&cipher, &result)
     })
 }
 
-pub fn create_userdata(iv: &Vec<u8>, userdata: &str, key: &str) -> Vec<u8> {
-    let prefix = "comment1=cooking%20MCs;userdata=";
-    let suffix = ";comment2=%20like%20a%20pound%20of%20bacon";
-
-    // quote out ';' and '=' characters in the input
-    let userdata = userdata.clone();
-    let userdata = userdata.replace(";", "%3B");
-    let userdata = userdata.replace("=", "%3D");
-
-    let mut data: Vec<u8> = vec![];
-    let p1: Vec<u8> = prefix.bytes().collect();
-    let p2: Vec<u8> = userdata.bytes().collect();
-    let p3: Vec<u8> = suffix.bytes().collect();
-    data.extend(p1);
-    data.extend(p2);
-    data.extend(p3);
-
-    let length = data.len();
-    let padding = pad_pkcs7(&data, length + (16 - (length % 16)));
-    let encrypted = encrypt_aes_cbc(&iv, &padding, &key);
-    encrypted.clone()
-}
-
-pub fn is_admin(iv: &Vec<u8>, profile: &Vec<u8>, key: &str) -> bool {
-    let result = decrypt_aes_cbc(&iv, &profile, &key);
-    let profile = raw_to_string(&result);
-    let subsets: Vec<&str> = profile.split(';').collect();
-    for subset in subsets {
-        let result: Vec<&str> = subset.split('=').collect();
-        if result.len() == 2 && result[0] == "admin" && result[1] == "true" {
-            return true
-        }
-    }
-    false
+pub fn create_userdata<C: BlockCipher + ?Sized>(iv: &Vec<u8>, userdata: &str, cipher: &C) -> Vec<u8> {
+    let mut cookie = Form::new();
+    cookie.push("comment1", "cooking MCs");
+    cookie.push("userdata", userdata);
+    cookie.push("comment2", " like a pound of bacon");
+
+    // everything outside ASCII is escaped, so the bytes are plain ASCII.
+    let data = cookie.to_cookie().into_bytes();
+    let padding = Pkcs7.pad(&data, cipher.block_size());
+    cbc_encrypt(cipher, &iv, &padding)
+}
+
+/// an error if the decrypted cookie doesn't parse, which a tampered block can
+/// cause.
+pub fn is_admin<C: BlockCipher + ?Sized>(iv: &Vec<u8>, profile: &Vec<u8>,
+                                         cipher: &C) -> Result<bool, DecodeError> {
+    let result = cbc_decrypt(cipher, &iv, &profile);
+    let stripped = match Pkcs7.unpad(&result, cipher.block_size()) {
+        Ok(stripped) => stripped.to_vec(),
+        Err(_) => result
+    };
+    let cookie = try!(Form::parse_cookie(&raw_to_string(&stripped)));
+    Ok(cookie.get("admin") == Some("true"))
 }
@@ -0,0 +1,134 @@
+use conversion::*;
+
+/// ordered key/value pairs as found in query strings and cookies. a key may
+/// appear more than once.
+///
+/// escapes are the utf-8 bytes of each character. parsing otherwise follows
+/// what browsers do: pairs without an `=` get an empty value and malformed
+/// escapes are kept as they are. the only error is escapes that don't decode
+/// to utf-8.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Form {
+    pairs: Vec<(String, String)>,
+}
+
+impl Form {
+    pub fn new() -> Form {
+        Form { pairs: vec![] }
+    }
+
+    pub fn push(&mut self, key: &str, value: &str) {
+        self.pairs.push((key.to_string(), value.to_string()));
+    }
+
+    /// the first value for `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs.iter().find(|p| p.0 == key).map(|p| &p.1[..])
+    }
+
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.pairs.iter().filter(|p| p.0 == key).map(|p| &p.1[..]).collect()
+    }
+
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+
+    /// parse `application/x-www-form-urlencoded` data, e.g. `a=1&b=two+words`.
+    pub fn parse_urlencoded(s: &str) -> Result<Form, DecodeError> {
+        Form::parse(s, '&', true)
+    }
+
+    /// parse a `;` separated cookie, e.g. `a=1; b=two%20words`.
+    pub fn parse_cookie(s: &str) -> Result<Form, DecodeError> {
+        Form::parse(s, ';', false)
+    }
+
+    pub fn to_urlencoded(&self) -> String {
+        self.serialize("&", true)
+    }
+
+    pub fn to_cookie(&self) -> String {
+        self.serialize(";", false)
+    }
+
+    fn parse(s: &str, separator: char, plus_as_space: bool) -> Result<Form, DecodeError> {
+        let mut form = Form::new();
+        let mut start = 0;
+        // offsets count chars, as everywhere else `DecodeError` is used.
+        for raw in s.split(separator) {
+            let pair = if separator == ';' { raw.trim_left() } else { raw };
+            let at = start + raw.chars().count() - pair.chars().count();
+            start += raw.chars().count() + 1;
+            if pair.is_empty() {
+                continue;
+            }
+            let (key, value) = match pair.find('=') {
+                Some(i) => (&pair[..i], &pair[i + 1..]),
+                None => (pair, "")
+            };
+            let split = key.chars().count() + 1;
+            let key = try!(unescape(key, at, plus_as_space));
+            let value = try!(unescape(value, at + split, plus_as_space));
+            form.push(&key, &value);
+        }
+        Ok(form)
+    }
+
+    fn serialize(&self, separator: &str, plus_as_space: bool) -> String {
+        let pairs: Vec<String> = self.pairs.iter().map(|&(ref k, ref v)| {
+            format!("{}={}", escape(k, plus_as_space), escape(v, plus_as_space))
+        }).collect();
+        pairs.join(separator)
+    }
+}
+
+fn escape(s: &str, plus_as_space: bool) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == ' ' && plus_as_space {
+            result.push('+');
+        } else {
+            let mut buffer = [0; 4];
+            result.push_str(&encode_percent(c.encode_utf8(&mut buffer).as_bytes()));
+        }
+    }
+    result
+}
+
+// `offset` is where `s` starts in the whole input, in chars, for error reporting.
+fn unescape(s: &str, offset: usize, plus_as_space: bool) -> Result<String, DecodeError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut result = Vec::with_capacity(s.len());
+    // the char in `s` each byte of `result` came from.
+    let mut origins = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let escaped = if c == '%' && i + 2 < chars.len() {
+            chars[i + 1].to_digit(16).and_then(|h| chars[i + 2].to_digit(16).map(|l| 16 * h + l))
+        } else {
+            None
+        };
+        match escaped {
+            Some(x) => {
+                result.push(x as u8);
+                origins.push(i);
+                i += 3;
+            }
+            None => {
+                let c = if c == '+' && plus_as_space { ' ' } else { c };
+                let mut buffer = [0; 4];
+                for &b in c.encode_utf8(&mut buffer).as_bytes() {
+                    result.push(b);
+                    origins.push(i);
+                }
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(result).map_err(|e| {
+        let at = origins[e.utf8_error().valid_up_to()];
+        DecodeError::InvalidCharacter('\u{fffd}', offset + at)
+    })
+}
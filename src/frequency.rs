@@ -0,0 +1,349 @@
+// English n-gram frequencies, measured over the text of "The Adventures of
+// Sherlock Holmes" with every run of whitespace collapsed to a single space.
+// Each table is sorted by descending frequency; the bigram and trigram tables
+// keep only the most common entries.
+
+pub const ENGLISH_UNIGRAMS: &'static [(&'static [u8], f64)] = &[
+    (b" ", 0.186758), (b"e", 0.094494), (b"t", 0.067632), (b"a", 0.061428), (b"o", 0.059352),
+    (b"n", 0.050731), (b"h", 0.049687), (b"s", 0.047237), (b"i", 0.047112), (b"r", 0.043446),
+    (b"d", 0.032798), (b"l", 0.030123), (b"u", 0.023367), (b"m", 0.019757), (b"w", 0.018776),
+    (b"c", 0.018183), (b"y", 0.016046), (b"f", 0.015703), (b"g", 0.013821), (b",", 0.013652),
+    (b"p", 0.011886), (b".", 0.011091), (b"b", 0.010519), (b"\"", 0.0091), (b"v", 0.007807),
+    (b"I", 0.006744), (b"k", 0.006195), (b"'", 0.00267), (b"H", 0.002225), (b"-", 0.002042),
+    (b"T", 0.002037), (b"A", 0.001363), (b"S", 0.001363), (b"W", 0.001354), (b"?", 0.001317),
+    (b"M", 0.00131), (b"x", 0.000967), (b"B", 0.000858), (b"Y", 0.000813), (b"q", 0.000725),
+    (b"!", 0.000616), (b"j", 0.000608), (b"C", 0.000586), (b"O", 0.000557), (b"N", 0.000543),
+    (b"L", 0.00052), (b"E", 0.000423), (b"D", 0.000384), (b"R", 0.000365), (b";", 0.000361),
+    (b"F", 0.00034), (b"P", 0.000332), (b"G", 0.000279), (b"z", 0.000264), (b"J", 0.000205),
+    (b"V", 0.000152), (b"0", 0.000147), (b"K", 0.000139), (b"1", 0.000113), (b":", 0.000107),
+    (b"U", 8.22e-05), (b"8", 6.61e-05), (b"2", 6.25e-05), (b"4", 3.93e-05), (b"Q", 3.57e-05),
+    (b"7", 3.22e-05), (b"5", 2.86e-05), (b"3", 2.68e-05), (b"6", 2.5e-05), (b"9", 2.32e-05),
+    (b"X", 1.43e-05), (b"(", 8.93e-06), (b")", 8.93e-06), (b"&", 8.93e-06), (b"Z", 3.57e-06),
+    (b"/", 1.79e-06),
+];
+
+pub const ENGLISH_BIGRAMS: &'static [(&'static [u8], f64)] = &[
+    (b"e ", 0.032149), (b" t", 0.024737), (b"th", 0.021026), (b"he", 0.020504), (b" a", 0.018937),
+    (b"d ", 0.018328), (b"t ", 0.017467), (b"s ", 0.015991), (b"in", 0.0136), (b" h", 0.013023),
+    (b" w", 0.013015), (b"er", 0.012687), (b", ", 0.012594), (b" s", 0.012219), (b"n ", 0.011918),
+    (b"an", 0.011529), (b"ha", 0.010239), (b"ou", 0.010081), (b"re", 0.009992), (b" i", 0.009863),
+    (b" o", 0.009835), (b"y ", 0.009147), (b"nd", 0.009102), (b"at", 0.008741), (b". ", 0.008643),
+    (b"r ", 0.008564), (b" m", 0.007953), (b"o ", 0.007941), (b"ed", 0.007776), (b"on", 0.007766),
+    (b"en", 0.007485), (b"hi", 0.007294), (b"is", 0.007156), (b" b", 0.007133), (b"to", 0.006992),
+    (b"as", 0.006747), (b" c", 0.006629), (b"it", 0.00656), (b" f", 0.00634), (b"ng", 0.006256),
+    (b"ve", 0.006252), (b"es", 0.006063), (b"f ", 0.005738), (b"me", 0.005668), (b"or", 0.005634),
+    (b"st", 0.005571), (b"ar", 0.005571), (b" I", 0.005546), (b"I ", 0.005241), (b"te", 0.005239),
+    (b"se", 0.005189), (b"of", 0.005119), (b"le", 0.004987), (b" \"", 0.004928), (b" d", 0.004826),
+    (b"a ", 0.004719), (b"ne", 0.004473), (b"h ", 0.00446), (b"g ", 0.004344), (b"ea", 0.004308),
+    (b" l", 0.004263), (b"ll", 0.004253), (b" p", 0.004154), (b"\" ", 0.004153), (b"nt", 0.004151),
+    (b"wa", 0.003911), (b"ro", 0.003895), (b"al", 0.003792), (b"ur", 0.00374), (b"be", 0.003711),
+    (b"wh", 0.003695), (b" n", 0.003529), (b"om", 0.003481), (b" y", 0.003475), (b"l ", 0.003454),
+    (b"de", 0.003433), (b"no", 0.003386), (b"ho", 0.003377), (b" r", 0.003343), (b"ti", 0.003327),
+    (b"ad", 0.003316), (b"co", 0.003295), (b"ee", 0.003293), (b"ch", 0.003284), (b"ma", 0.003206),
+    (b"ut", 0.003181), (b"yo", 0.003179), (b"ri", 0.003157), (b"ow", 0.003156), (b"el", 0.002995),
+    (b"ce", 0.002984), (b"wi", 0.002973), (b"ai", 0.002913), (b"sh", 0.002895), (b" e", 0.002868),
+    (b"ic", 0.002845), (b"li", 0.00282), (b"we", 0.002816), (b"ot", 0.002813), (b"us", 0.002727),
+    (b"si", 0.002664), (b"ly", 0.002662), (b"fo", 0.00265), (b"so", 0.002645), (b"la", 0.002637),
+    (b" u", 0.002618), (b"et", 0.0026), (b"oo", 0.002587), (b" g", 0.002528), (b"ra", 0.002525),
+    (b"m ", 0.002464), (b"av", 0.00245), (b"ul", 0.002441), (b"lo", 0.002439), (b"id", 0.002423),
+    (b"e,", 0.002375), (b"ol", 0.002321), (b"ca", 0.002309), (b"pe", 0.002305), (b"u ", 0.002273),
+    (b"ld", 0.002251), (b"un", 0.002235), (b"ss", 0.002171), (b"gh", 0.002159), (b"ta", 0.002155),
+    (b"po", 0.002153), (b"il", 0.002153), (b"do", 0.002098), (b".\"", 0.002092), (b"ke", 0.00208),
+    (b"im", 0.002075), (b"ac", 0.002026), (b"sa", 0.002025), (b"ge", 0.001912), (b" H", 0.001901),
+    (b"e.", 0.001899), (b"k ", 0.001882), (b"io", 0.00186), (b"rs", 0.001846), (b"my", 0.001837),
+    (b"ay", 0.001824), (b"tr", 0.001824), (b"s,", 0.001781), (b"ry", 0.00176), (b"ir", 0.001744),
+    (b"tt", 0.001717), (b"Th", 0.001703), (b"am", 0.001703), (b"nc", 0.001694), (b"w ", 0.001689),
+    (b"ig", 0.001615), (b"di", 0.001605), (b"em", 0.001596), (b"up", 0.001589), (b"ie", 0.001578),
+    (b"wo", 0.001565), (b"mo", 0.001535), (b"s.", 0.001501), (b"rt", 0.001496), (b"ev", 0.001488),
+    (b"ht", 0.001483), (b"d,", 0.00148), (b"ni", 0.001467), (b"os", 0.001462), (b"mi", 0.001437),
+    (b"ec", 0.001424), (b"r.", 0.001421), (b"ns", 0.001408), (b"rd", 0.001406), (b"bl", 0.001388),
+    (b"fr", 0.001378), (b"ck", 0.001358), (b" T", 0.001356), (b"pa", 0.001342), (b"t,", 0.001337),
+    (b"fa", 0.001313), (b"pr", 0.001306), (b"t.", 0.001276), (b"bu", 0.001267), (b" v", 0.001245),
+    (b"su", 0.001237), (b"fe", 0.001233), (b"r,", 0.001224), (b"ab", 0.00122), (b"n,", 0.001219),
+    (b"ct", 0.001204), (b" S", 0.001201), (b"tl", 0.001201), (b"fi", 0.001192), (b" M", 0.001149),
+    (b"Ho", 0.001065), (b"ug", 0.001058), (b"ap", 0.001042), (b"ts", 0.001036), (b"d.", 0.001013),
+    (b"rn", 0.001006), (b"\"I", 0.000992), (b"ag", 0.000986), (b"pl", 0.000968), (b"tu", 0.000958),
+    (b"vi", 0.000951), (b"p ", 0.000943), (b"bo", 0.000942), (b"mp", 0.00094), (b"wn", 0.000926),
+    (b"cl", 0.000924), (b"rr", 0.000924), (b" A", 0.000918), (b"?\"", 0.000917), (b"ey", 0.00091),
+    (b"na", 0.00089), (b"lm", 0.000886), (b"iv", 0.000883), (b"go", 0.000859), (b",\"", 0.000852),
+    (b"ef", 0.000851), (b"oc", 0.000849), (b"y,", 0.000847), (b"uc", 0.000836), (b"if", 0.000836),
+    (b" k", 0.000834), (b"da", 0.000829), (b"op", 0.000829), (b"ak", 0.000829), (b" W", 0.000824),
+    (b"' ", 0.000818), (b"ov", 0.000817), (b"ok", 0.000811), (b"n.", 0.000809), (b"It", 0.000806),
+    (b"ga", 0.000786), (b"dr", 0.000781), (b"cr", 0.000781), (b"ba", 0.000774), (b"od", 0.000772),
+    (b"sp", 0.000754), (b"ds", 0.000743), (b"ep", 0.00074), (b"br", 0.000731), (b"nk", 0.000725),
+    (b"ex", 0.000722), (b"qu", 0.000722), (b"He", 0.000708), (b"y.", 0.000704), (b"ye", 0.0007),
+    (b"gr", 0.0007), (b"pp", 0.000683), (b"ew", 0.000674), (b"mu", 0.000666), (b"'s", 0.000658),
+    (b"aw", 0.000658), (b"ei", 0.000656), (b" B", 0.000654), (b"ru", 0.000649), (b"ci", 0.000643),
+    (b"ty", 0.000641), (b"by", 0.000625), (b"kn", 0.000624), (b"cu", 0.000608), (b"ff", 0.000599),
+    (b"pi", 0.000595), (b"ia", 0.000588), (b"ui", 0.000583), (b"gi", 0.000581), (b"ny", 0.000577),
+    (b"hr", 0.000572), (b"Yo", 0.00057), (b"nl", 0.00057), (b"ue", 0.000568), (b"Mr", 0.000565),
+    (b"rk", 0.000563), (b"lf", 0.00055), (b"lu", 0.000545), (b"\"T", 0.000541), (b"ki", 0.000527),
+    (b"rl", 0.000525), (b"l,", 0.000525), (b"ft", 0.000524), (b"au", 0.000506), (b"ys", 0.000504),
+    (b"rm", 0.000504), (b"sc", 0.000493), (b"ob", 0.000493), (b"gl", 0.000493), (b"Wh", 0.000493),
+    (b"tw", 0.000491), (b"hu", 0.000488), (b" q", 0.000466), (b"\"'", 0.000459), (b"oi", 0.000457),
+    (b"\"W", 0.000454), (b"um", 0.000449), (b"ls", 0.000445), (b"nn", 0.000438), (b"af", 0.000438),
+    (b"pu", 0.000431), (b"fu", 0.000429), (b"An", 0.000427), (b"We", 0.000427), (b"dy", 0.000425),
+    (b"h,", 0.000423), (b"m.", 0.000423), (b" L", 0.00042), (b"St", 0.00042), (b"\"Y", 0.000418),
+    (b" C", 0.000409), (b"sm", 0.000397), (b"No", 0.000393), (b"oa", 0.000388), (b"ub", 0.000382),
+    (b" j", 0.000382), (b"ms", 0.000377), (b"g,", 0.000368), (b"sl", 0.000366), (b"sk", 0.000365),
+    (b"Sh", 0.000361), (b"dl", 0.000361), (b"; ", 0.000361), (b"w,", 0.000361), (b"ua", 0.000359),
+    (b"lt", 0.000357), (b"m,", 0.000357), (b" '", 0.000354), (b"--", 0.000345), (b"gu", 0.000343),
+    (b"ib", 0.000341), (b"dd", 0.00034), (b"ps", 0.000338), (b"Bu", 0.000338), (b"\"A", 0.000338),
+    (b"ip", 0.000331), (b"cc", 0.000331), (b"g.", 0.000331), (b"rg", 0.000329), (b"o,", 0.000327),
+    (b"mb", 0.000325), (b"ud", 0.000322), (b"va", 0.000316), (b"l.", 0.000316), (b".'", 0.000316),
+    (b" Y", 0.000313), (b"ju", 0.000309), (b"fl", 0.000306), (b"! ", 0.000304), (b"rc", 0.0003),
+    (b"du", 0.000298), (b"eg", 0.000298), (b" O", 0.000297), (b"hy", 0.000288), (b"k,", 0.000277),
+    (b"sw", 0.000275), (b"ik", 0.000275), (b"rv", 0.000273), (b"n'", 0.000273), (b" R", 0.000272),
+    (b" F", 0.000268), (b"mm", 0.000268), (b"bi", 0.000261), (b"nu", 0.000259), (b" D", 0.000257),
+    (b"wr", 0.000257), (b"bs", 0.000254), (b"e-", 0.000254), (b"o.", 0.00025), (b"? ", 0.000247),
+    (b"pt", 0.000247), (b"nf", 0.000241), (b"xp", 0.000239), (b"\"H", 0.000239),
+    (b"!\"", 0.000236), (b"og", 0.000234), (b"e?", 0.000231), (b"ks", 0.000227),
+    (b"\"N", 0.000227), (b"lk", 0.000225), (b" N", 0.00022), (b"vo", 0.000218), (b" G", 0.000216),
+    (b" P", 0.000214), (b"d-", 0.000214), (b"Wa", 0.000214), (b"gs", 0.000213), (b"Ye", 0.000207),
+    (b"Co", 0.000205), (b"In", 0.000204), (b"gg", 0.000204), (b"Lo", 0.000202), (b"tc", 0.0002),
+    (b"dg", 0.000198), (b"rp", 0.000198), (b"Wi", 0.000197), (b",'", 0.000193), (b"Ha", 0.000193),
+    (b"ws", 0.000191), (b"nv", 0.000191), (b" J", 0.000189), (b"yi", 0.000188), (b"'t", 0.000188),
+    (b"n?", 0.000186), (b"bt", 0.000182), (b"gn", 0.000182), (b"k.", 0.000182), (b"A ", 0.000179),
+    (b" E", 0.000179), (b"t?", 0.000179), (b"dv", 0.000177), (b"oy", 0.000177), (b"u.", 0.000177),
+    (b"eo", 0.000177), (b"\"B", 0.000177), (b"\"O", 0.000177), (b"My", 0.000175),
+    (b"u,", 0.000173), (b"h.", 0.000173), (b"je", 0.000172), (b"ph", 0.000168), (b"Le", 0.000164),
+    (b"xc", 0.000164), (b"Mi", 0.000164), (b"On", 0.000163), (b"As", 0.000161), (b"r'", 0.000159),
+    (b"xt", 0.000159), (b"Oh", 0.000159), (b"p,", 0.000155), (b"c ", 0.000154), (b"?'", 0.000154),
+    (b"g-", 0.00015), (b"e'", 0.00015), (b"rh", 0.000147), (b"Ma", 0.000145), (b"lv", 0.000143),
+    (b"o-", 0.000143), (b"s?", 0.000143), (b"lp", 0.000141), (b"Do", 0.000141), (b"Fr", 0.000139),
+    (b"oe", 0.000139), (b"ek", 0.000136), (b"-s", 0.000136), (b"yt", 0.000136), (b"xa", 0.000134),
+    (b"eh", 0.000132), (b"t-", 0.00013), (b"Ca", 0.000129), (b"-c", 0.000127), (b"If", 0.000127),
+    (b"-h", 0.000125), (b"sy", 0.000125), (b"w.", 0.000125), (b"So", 0.000125), (b" K", 0.000123),
+    (b"Ba", 0.000122), (b"ze", 0.000122), (b"-t", 0.000122), (b"t'", 0.000122), (b"l-", 0.000118),
+    (b"cy", 0.000118), (b"wl", 0.000116), (b"Go", 0.000116), (b"'I", 0.000116), (b"Pr", 0.000114),
+    (b"-r", 0.000114), (b"e!", 0.000114), (b"Ro", 0.000114), (b"\"M", 0.000113), (b"f,", 0.000111),
+    (b"lw", 0.000109), (b"Al", 0.000109), (b"r-", 0.000109), (b"-b", 0.000109), (b"Hi", 0.000107),
+    (b"\"S", 0.000107), (b"dn", 0.000107), (b"Si", 0.000105), (b"rw", 0.000105), (b"Br", 0.000104),
+    (b"Bo", 0.000102), (b"sn", 9.83e-05), (b"ix", 9.65e-05), (b"Be", 9.47e-05), (b"To", 9.47e-05),
+    (b"jo", 9.47e-05), (b"Mo", 9.47e-05), (b"kl", 9.29e-05), (b"yl", 9.29e-05), (b"rf", 9.29e-05),
+    (b"y-", 9.11e-05), (b"-n", 9.11e-05), (b"n-", 9.11e-05), (b"-m", 9.11e-05), (b"uf", 8.93e-05),
+    (b"x ", 8.93e-05), (b"b ", 8.76e-05), (b"bb", 8.76e-05), (b"p.", 8.76e-05), (b"-a", 8.76e-05),
+    (b"rb", 8.58e-05), (b": ", 8.58e-05), (b"'T", 8.58e-05), (b"r?", 8.58e-05), (b"lr", 8.58e-05),
+    (b"tm", 8.58e-05), (b"iz", 8.4e-05), (b"e;", 8.4e-05), (b"f.", 8.4e-05), (b"d?", 8.4e-05),
+    (b"oh", 8.22e-05), (b"s-", 8.22e-05), (b"-l", 8.22e-05), (b"En", 8.04e-05), (b" 1", 8.04e-05),
+    (b"Fo", 8.04e-05), (b"E ", 7.86e-05), (b"I.", 7.86e-05), (b"At", 7.86e-05), (b"eq", 7.86e-05),
+    (b"y?", 7.86e-05), (b"Ru", 7.86e-05), (b"Mc", 7.86e-05), (b"-p", 7.68e-05), (b"Su", 7.68e-05),
+    (b"\"P", 7.68e-05), (b"t!", 7.68e-05), (b" V", 7.5e-05), (b"Ne", 7.5e-05), (b"!'", 7.5e-05),
+    (b"cC", 7.5e-05), (b"Jo", 7.33e-05), (b"sf", 7.33e-05), (b"'W", 7.33e-05), (b"s'", 7.15e-05),
+    (b"Cl", 7.15e-05), (b"Ar", 7.15e-05), (b"Ja", 6.97e-05), (b"Pe", 6.97e-05), (b"Ge", 6.97e-05),
+    (b"a,", 6.97e-05), (b"-d", 6.97e-05), (b"tn", 6.97e-05), (b"\"t", 6.79e-05), (b"sb", 6.79e-05),
+    (b"a.", 6.61e-05), (b"Hu", 6.61e-05), (b"-w", 6.43e-05), (b"f-", 6.43e-05), (b"La", 6.43e-05),
+    (b"bj", 6.25e-05), (b"eb", 6.25e-05), (b"xe", 6.25e-05), (b"'Y", 6.25e-05), (b"I'", 6.25e-05),
+    (b"0 ", 6.25e-05), (b"00", 6.25e-05), (b"bv", 6.08e-05), (b"o'", 6.08e-05), (b"'c", 6.08e-05),
+    (b"gt", 6.08e-05), (b"Ki", 6.08e-05), (b"'l", 6.08e-05), (b"Re", 5.9e-05), (b"s;", 5.9e-05),
+    (b"I,", 5.9e-05), (b"hn", 5.9e-05), (b"Dr", 5.9e-05), (b"d!", 5.9e-05), (b"Ve", 5.9e-05),
+    (b"xi", 5.9e-05), (b"az", 5.72e-05), (b"ka", 5.72e-05), (b"s!", 5.72e-05), (b"y'", 5.72e-05),
+    (b"Ah", 5.72e-05), (b"k-", 5.54e-05), (b"nq", 5.54e-05), (b"nj", 5.54e-05), (b"m?", 5.54e-05),
+    (b"HE", 5.36e-05), (b"vy", 5.36e-05), (b"-f", 5.36e-05), (b"Ch", 5.36e-05), (b"mn", 5.36e-05),
+    (b"\"D", 5.36e-05), (b"py", 5.36e-05), (b"hs", 5.18e-05), (b"Po", 5.18e-05), (b"Tu", 5.18e-05),
+    (b"Am", 5.18e-05), (b"gy", 5e-05), (b"Pa", 5e-05), (b"d'", 5e-05), (b"nr", 5e-05),
+    (b"ox", 5e-05), (b"df", 4.82e-05), (b"By", 4.82e-05), (b"ya", 4.82e-05), (b"Sa", 4.82e-05),
+    (b"yd", 4.82e-05), (b"'A", 4.82e-05), (b"-e", 4.65e-05),
+];
+
+pub const ENGLISH_TRIGRAMS: &'static [(&'static [u8], f64)] = &[
+    (b" th", 0.016693), (b"the", 0.012576), (b"he ", 0.012569), (b"nd ", 0.006601),
+    (b" an", 0.00627), (b"and", 0.006022), (b"ed ", 0.005857), (b" to", 0.005514),
+    (b"at ", 0.005321), (b"to ", 0.005214), (b" of", 0.004951), (b"ing", 0.004935),
+    (b"is ", 0.0048), (b" ha", 0.00476), (b"of ", 0.004694), (b" in", 0.004621),
+    (b" I ", 0.004526), (b" a ", 0.004501), (b"as ", 0.004374), (b"ng ", 0.004237),
+    (b"er ", 0.004101), (b"hat", 0.003985), (b" he", 0.00384), (b" wh", 0.003624),
+    (b"her", 0.003547), (b"re ", 0.003436), (b"e t", 0.003404), (b"in ", 0.00339),
+    (b" be", 0.003331), (b" wa", 0.0033), (b"tha", 0.003266), (b", a", 0.003218),
+    (b"d t", 0.003182), (b"you", 0.003102), (b" yo", 0.003052), (b" hi", 0.002993),
+    (b"his", 0.00293), (b"n t", 0.002891), (b"ere", 0.002836), (b"t t", 0.002741),
+    (b"en ", 0.00272), (b" co", 0.002707), (b" wi", 0.002687), (b"e a", 0.002643),
+    (b"e s", 0.002637), (b"e w", 0.002602), (b"on ", 0.002575), (b"ve ", 0.002552),
+    (b"was", 0.002521), (b" it", 0.002453), (b"\" \"", 0.002441), (b" no", 0.002394),
+    (b"s a", 0.002282), (b"ou ", 0.002259), (b"me ", 0.002246), (b"ut ", 0.002244),
+    (b"ll ", 0.002226), (b"ver", 0.002209), (b"e, ", 0.002169), (b" ma", 0.002164),
+    (b"e o", 0.002153), (b"ly ", 0.002119), (b" fo", 0.002117), (b"ave", 0.002116),
+    (b"for", 0.002098), (b".\" ", 0.002092), (b"or ", 0.002046), (b" sh", 0.002005),
+    (b"e h", 0.002003), (b"ch ", 0.001994), (b" is", 0.001985), (b"it ", 0.001967),
+    (b"an ", 0.001932), (b"t i", 0.001932), (b" we", 0.001921), (b"ter", 0.001905),
+    (b" so", 0.00189), (b"thi", 0.001885), (b"ent", 0.001878), (b"all", 0.001862),
+    (b"ld ", 0.001851), (b" as", 0.001826), (b"ith", 0.001823), (b"e i", 0.001823),
+    (b"our", 0.001803), (b"ad ", 0.001801), (b" my", 0.001799), (b"d a", 0.001785),
+    (b"s t", 0.001778), (b"th ", 0.001762), (b" re", 0.001762), (b"f t", 0.001756),
+    (b"e c", 0.001742), (b" sa", 0.001737), (b". \"", 0.001737), (b"e m", 0.001735),
+    (b"hav", 0.00173), (b"st ", 0.001706), (b" do", 0.001687), (b"s, ", 0.001685),
+    (b"wit", 0.001681), (b"le ", 0.001678), (b" on", 0.001647), (b"whi", 0.001647),
+    (b"es ", 0.001637), (b"my ", 0.00163), (b" me", 0.001622), (b". I", 0.001621),
+    (b" ca", 0.00161), (b"t w", 0.001603), (b" se", 0.001594), (b" st", 0.001585),
+    (b"oul", 0.001583), (b"not", 0.001569), (b"d h", 0.001562), (b"uld", 0.001558),
+    (b"ion", 0.001526), (b"one", 0.001524), (b"had", 0.00151), (b"t a", 0.001501),
+    (b"ght", 0.001483), (b"e. ", 0.001463), (b"ear", 0.001444), (b" at", 0.001442),
+    (b"t h", 0.001437), (b"ich", 0.001435), (b"d, ", 0.001413), (b"hic", 0.001412),
+    (b" up", 0.001408), (b"hin", 0.001396), (b"ry ", 0.001362), (b"ot ", 0.001351),
+    (b", w", 0.001349), (b"e b", 0.001349), (b" li", 0.001346), (b"o t", 0.001346),
+    (b" fr", 0.00132), (b"n a", 0.001301), (b"The", 0.001295), (b"t o", 0.001295),
+    (b"eve", 0.00129), (b"id ", 0.00129), (b"ur ", 0.001281), (b"hen", 0.001281),
+    (b" wo", 0.001267), (b", t", 0.001263), (b"hou", 0.001263), (b"e f", 0.001258),
+    (b"ne ", 0.001254), (b"r t", 0.001247), (b"s w", 0.001247), (b"se ", 0.001238),
+    (b"r. ", 0.001238), (b"igh", 0.001238), (b"ome", 0.001235), (b"e d", 0.001231),
+    (b"out", 0.001206), (b"t, ", 0.001201), (b" al", 0.001199), (b"ce ", 0.001194),
+    (b"s. ", 0.001186), (b"s o", 0.001185), (b"om ", 0.001167), (b"ay ", 0.001158),
+    (b" bu", 0.001147), (b" Th", 0.001131), (b"r, ", 0.001131), (b"nce", 0.001128),
+    (b"ess", 0.001128), (b"nt ", 0.001122), (b"man", 0.001113), (b"n, ", 0.001111),
+    (b"d i", 0.001106), (b"e l", 0.001104), (b" ho", 0.001101), (b"be ", 0.001099),
+    (b" fa", 0.001097), (b"int", 0.001085), (b"d b", 0.001077), (b"d s", 0.001076),
+    (b"res", 0.001063), (b"aid", 0.001061), (b". T", 0.00106), (b"ow ", 0.001052),
+    (b" mo", 0.001051), (b" la", 0.001047), (b"rea", 0.001045), (b" ou", 0.001045),
+    (b"red", 0.001042), (b"e p", 0.00104), (b"een", 0.001038), (b" si", 0.001035),
+    (b"tio", 0.001024), (b"ery", 0.001017), (b"are", 0.001015), (b"oun", 0.001013),
+    (b" Ho", 0.001002), (b"mes", 0.001002), (b"d w", 0.000995), (b"y t", 0.000993),
+    (b" \"I", 0.00099), (b"n h", 0.000979), (b" de", 0.000979), (b"fro", 0.000977),
+    (b"s i", 0.000974), (b"t. ", 0.000974), (b"I h", 0.000961), (b"d o", 0.000961),
+    (b"rom", 0.00096), (b" pr", 0.000954), (b"est", 0.000951), (b"ell", 0.000943),
+    (b"s s", 0.000935), (b" lo", 0.000933), (b"she", 0.000929), (b"ht ", 0.000927),
+    (b"ill", 0.000927), (b"him", 0.000926), (b" pa", 0.000926), (b" su", 0.000926),
+    (b"und", 0.000922), (b"?\" ", 0.000917), (b"ugh", 0.000908), (b"han", 0.000906),
+    (b"ind", 0.000904), (b"ain", 0.000893), (b"pon", 0.000892), (b"s h", 0.000892),
+    (b"r a", 0.00089), (b"ble", 0.000881), (b"e r", 0.000881), (b"sai", 0.000881),
+    (b"ted", 0.000877), (b"t s", 0.000877), (b"sta", 0.000876), (b"but", 0.000874),
+    (b"tte", 0.00087), (b"com", 0.000865), (b"Hol", 0.000863), (b" le", 0.000856),
+    (b" di", 0.000854), (b",\" ", 0.000852), (b" fi", 0.000851), (b"ate", 0.000849),
+    (b"wer", 0.000847), (b"n o", 0.000847), (b"d. ", 0.000843), (b"own", 0.00084),
+    (b"y a", 0.000834), (b"et ", 0.000831), (b"cou", 0.000829), (b"lme", 0.000827),
+    (b"upo", 0.000827), (b"olm", 0.000824), (b"ss ", 0.00082), (b". H", 0.000818),
+    (b"tle", 0.000813), (b" ar", 0.000811), (b"see", 0.000804), (b"oth", 0.000801),
+    (b", b", 0.000801), (b"er,", 0.000801), (b"whe", 0.000797), (b"ore", 0.000793),
+    (b"ers", 0.00079), (b"t I", 0.000783), (b"ine", 0.000779), (b"con", 0.000775),
+    (b" po", 0.000774), (b"y, ", 0.000772), (b"sho", 0.000768), (b"ide", 0.000767),
+    (b"ove", 0.000765), (b" ve", 0.000765), (b"ned", 0.000765), (b"es,", 0.000763),
+    (b"wn ", 0.000761), (b"ste", 0.000758), (b"ard", 0.000758), (b"It ", 0.000754),
+    (b" go", 0.000752), (b"n w", 0.000749), (b"now", 0.000747), (b"h t", 0.000745),
+    (b"ive", 0.000743), (b"h a", 0.000736), (b"we ", 0.000736), (b"d m", 0.000731),
+    (b"per", 0.000727), (b"oug", 0.000724), (b"bee", 0.000722), (b"te ", 0.00072),
+    (b"der", 0.000718), (b"y s", 0.000715), (b"ous", 0.000713), (b" mi", 0.000711),
+    (b"I s", 0.000711), (b"e n", 0.000709), (b"ck ", 0.000706), (b"s f", 0.000704),
+    (b"g t", 0.000704), (b"ust", 0.000704), (b"n i", 0.000697), (b"ar ", 0.000692),
+    (b"o m", 0.00069), (b"hea", 0.000686), (b"y w", 0.000686), (b", s", 0.000681),
+    (b"ame", 0.000679), (b"t m", 0.000679), (b"str", 0.000675), (b"rie", 0.000675),
+    (b"ed,", 0.000668), (b"r h", 0.000665), (b"itt", 0.000665), (b"tur", 0.000663),
+    (b"e e", 0.000661), (b"tin", 0.000658), (b"o h", 0.000654), (b"rou", 0.000654),
+    (b"ure", 0.000652), (b"who", 0.00065), (b" un", 0.000649), (b"nin", 0.000649),
+    (b" br", 0.000649), (b" ro", 0.000649), (b"ked", 0.000647), (b" ch", 0.000645),
+    (b"dow", 0.000643), (b"ast", 0.000641), (b"so ", 0.00064), (b"I w", 0.000638),
+    (b" cl", 0.000634), (b"use", 0.000633), (b"som", 0.000631), (b"d n", 0.000629),
+    (b"n. ", 0.000625), (b"s n", 0.000625), (b"\"I ", 0.000625), (b"ead", 0.000624),
+    (b" ex", 0.000624), (b"r o", 0.000622), (b"d f", 0.00062), (b"tra", 0.000618),
+    (b" tr", 0.000616), (b"ke ", 0.000615), (b" ev", 0.000615), (b"e g", 0.000615),
+    (b"s c", 0.000613), (b"al ", 0.000611), (b" ne", 0.000611), (b"r w", 0.000611),
+    (b"tho", 0.000611), (b"lit", 0.000609), (b"nde", 0.000604), (b"wou", 0.000604),
+    (b"a s", 0.000604), (b"ath", 0.000604), (b"n s", 0.000604), (b" mu", 0.000604),
+    (b"ati", 0.000602), (b"by ", 0.000597), (b", h", 0.000597), (b"sel", 0.000593),
+    (b"s m", 0.00059), (b"\" s", 0.00059), (b"r s", 0.000588), (b" He", 0.000588),
+    (b", I", 0.000588), (b"ed.", 0.000588), (b"'s ", 0.000586), (b"rs ", 0.000586),
+    (b"t b", 0.000583), (b"f a", 0.000583), (b"ven", 0.000581), (b"mor", 0.000575),
+    (b"s b", 0.000575), (b"ook", 0.000575), (b"ist", 0.000574), (b"o s", 0.000574),
+    (b" dr", 0.00057), (b"You", 0.00057), (b" by", 0.000568), (b" kn", 0.000566),
+    (b"y o", 0.000565), (b"rd ", 0.000563), (b" ba", 0.000563), (b" da", 0.000561),
+    (b" fe", 0.000559), (b"way", 0.000558), (b"abl", 0.000558), (b"son", 0.000556),
+    (b"ts ", 0.000556), (b"y h", 0.000554), (b"ect", 0.000552), (b"led", 0.000552),
+    (b"art", 0.000552), (b"rin", 0.000552), (b"ace", 0.00055), (b"He ", 0.000549),
+    (b"lea", 0.000549), (b"sha", 0.000549), (b"ake", 0.000547), (b"ttl", 0.000547),
+    (b" en", 0.000547), (b"min", 0.000545), (b"sin", 0.000545), (b"d I", 0.000543),
+    (b"men", 0.000543), (b" It", 0.000543), (b"sed", 0.000541), (b" \"T", 0.000541),
+    (b"y. ", 0.00054), (b"anc", 0.000536), (b"has", 0.000536), (b". A", 0.000534),
+    (b"de ", 0.000534), (b" Mr", 0.000532), (b"nte", 0.000531), (b"ssi", 0.000531),
+    (b"air", 0.000531), (b"wha", 0.000531), (b"er.", 0.000529), (b"eat", 0.000529),
+    (b"o a", 0.000527), (b"g a", 0.000524), (b" ab", 0.000522), (b"e y", 0.000522),
+    (b"att", 0.00052), (b"l t", 0.00052), (b"n m", 0.00052), (b", i", 0.00052), (b"us ", 0.000516),
+    (b"ey ", 0.000516), (b"y f", 0.000516), (b"ose", 0.000513), (b"can", 0.000513),
+    (b"ran", 0.000513), (b"m t", 0.000513), (b"nly", 0.000513), (b"end", 0.000511),
+    (b"nto", 0.000509), (b"ee ", 0.000509), (b" us", 0.000507), (b"ant", 0.000507),
+    (b"y c", 0.000507), (b"sti", 0.000506), (b"im ", 0.000504), (b"ack", 0.000504),
+    (b"no ", 0.000502), (b"\"Th", 0.0005), (b"wil", 0.0005), (b" ta", 0.000499),
+    (b"o b", 0.000499), (b"ten", 0.000497), (b"nti", 0.000497), (b"a c", 0.000497),
+    (b"h h", 0.000497), (b"I c", 0.000493), (b"ock", 0.000491), (b"pro", 0.000491),
+    (b"t y", 0.000491), (b"es.", 0.000491), (b"ge ", 0.000488), (b" cr", 0.000488),
+    (b"l, ", 0.000486), (b"loo", 0.000486), (b"ood", 0.000486), (b"I a", 0.000486),
+    (b"Mr.", 0.000486), (b"roo", 0.000484), (b" gr", 0.000484), (b"pre", 0.000481),
+    (b"any", 0.000479), (b"how", 0.000479), (b"nge", 0.000477), (b"ho ", 0.000477),
+    (b"tan", 0.000477), (b"thr", 0.000472), (b"d d", 0.00047), (b"kin", 0.000466),
+    (b" qu", 0.000466), (b"ds ", 0.000466), (b" am", 0.000466), (b" pe", 0.000465),
+    (b"f h", 0.000463), (b"a l", 0.000459), (b"uch", 0.000459), (b"up ", 0.000459),
+    (b"pen", 0.000459), (b" \"'", 0.000459), (b" or", 0.000457), (b"ite", 0.000457),
+    (b"cha", 0.000454), (b" \"W", 0.000454), (b"hal", 0.000454), (b"ord", 0.000452),
+    (b"nes", 0.000449), (b"ree", 0.000449), (b"oom", 0.000449), (b"sid", 0.000447),
+    (b"ime", 0.000445), (b"ene", 0.000443), (b"old", 0.000443), (b"t f", 0.000443),
+    (b"ong", 0.000443), (b"kno", 0.000443), (b". W", 0.000443), (b", f", 0.000443),
+    (b"lan", 0.000441), (b"ner", 0.000441), (b"hed", 0.000441), (b"ice", 0.000441),
+    (b"den", 0.00044), (b"y m", 0.00044), (b"urn", 0.00044), (b"enc", 0.000438),
+    (b"y i", 0.000436), (b"ink", 0.000436), (b"arr", 0.000434), (b"ir ", 0.000434),
+    (b"r i", 0.000434), (b"ang", 0.000432), (b"eas", 0.000432), (b"age", 0.000432),
+    (b"on,", 0.000432), (b"lin", 0.000432), (b"ort", 0.000432), (b"y b", 0.000431),
+    (b". S", 0.000431), (b"elf", 0.000429), (b"din", 0.000429), (b"ins", 0.000427),
+    (b" bo", 0.000427), (b"ew ", 0.000427), (b"s p", 0.000427), (b"ark", 0.000425),
+    (b"ely", 0.000423), (b"nk ", 0.000422), (b"ons", 0.00042), (b"ass", 0.00042),
+    (b"d y", 0.00042), (b"tre", 0.00042), (b"am ", 0.00042), (b" \"Y", 0.000418),
+    (b"low", 0.000415), (b"pos", 0.000413), (b"rem", 0.000413), (b"mar", 0.000411),
+    (b"pla", 0.000407), (b"g o", 0.000407), (b"ise", 0.000406), (b"ied", 0.000406),
+    (b"ope", 0.000406), (b" ti", 0.000406), (b"ade", 0.000404), (b"ppe", 0.000404),
+    (b"fac", 0.000404), (b" St", 0.000404), (b"gh ", 0.000404), (b"lle", 0.0004), (b"win", 0.0004),
+    (b" te", 0.000398), (b"owe", 0.000398), (b"app", 0.000398), (b"d c", 0.000398),
+    (b"les", 0.000398), (b"d u", 0.000395), (b"r f", 0.000395), (b"h, ", 0.000395),
+    (b"eme", 0.000395), (b"t c", 0.000395), (b"tor", 0.000393), (b"rne", 0.000393),
+    (b" ye", 0.000391), (b"sto", 0.000388), (b"too", 0.000388), (b"y d", 0.000388),
+    (b"rec", 0.000386), (b"wor", 0.000386), (b"che", 0.000384), (b" sp", 0.000384),
+    (b"cas", 0.000384), (b"ny ", 0.000382), (b"rri", 0.000382), (b"bou", 0.000382),
+    (b"r m", 0.000382), (b"act", 0.000379), (b"oor", 0.000379), (b"e.\"", 0.000379),
+    (b" pl", 0.000377), (b"ese", 0.000377), (b"unt", 0.000375), (b"lf ", 0.000375),
+    (b"nd,", 0.000375), (b", o", 0.000373), (b"s l", 0.000373), (b"e u", 0.000372),
+    (b"qui", 0.000372), (b"s e", 0.00037), (b"ema", 0.00037), (b" tw", 0.00037), (b"do ", 0.00037),
+    (b"on.", 0.000368), (b"d l", 0.000368), (b"rat", 0.000368), (b"hes", 0.000366),
+    (b"I t", 0.000366), (b"eet", 0.000366), (b"tim", 0.000366), (b" ov", 0.000365),
+    (b"fin", 0.000363), (b"ll,", 0.000363), (b" pu", 0.000363), (b"ty ", 0.000361),
+    (b"And", 0.000361), (b"s d", 0.000361), (b" ra", 0.000361), (b"tai", 0.000361),
+    (b"abo", 0.000361), (b"ien", 0.000359), (b"n f", 0.000359), (b"a m", 0.000359),
+    (b"day", 0.000359), (b"tel", 0.000357), (b"lly", 0.000357), (b"urs", 0.000357),
+    (b"hey", 0.000356), (b"k t", 0.000356), (b"lon", 0.000356), (b"mon", 0.000354),
+    (b"t d", 0.000354), (b"o d", 0.000352), (b"ish", 0.000348), (b"m a", 0.000348),
+    (b"ded", 0.000347), (b"loc", 0.000347), (b"nds", 0.000347), (b"har", 0.000347),
+    (b"ng,", 0.000347), (b"mat", 0.000347), (b"tly", 0.000345), (b"h w", 0.000345),
+    (b"' \"", 0.000345), (b"til", 0.000343), (b"ire", 0.000343), (b" ge", 0.000343),
+    (b"may", 0.000343), (b"ron", 0.000341), (b"She", 0.000341), (b"ost", 0.000341),
+    (b"m, ", 0.000341), (b"ase", 0.00034), (b"sit", 0.00034), (b"a p", 0.00034),
+    (b" ri", 0.000338), (b"o w", 0.000338), (b" ag", 0.000338), (b"rse", 0.000338),
+    (b" \"A", 0.000338), (b"y p", 0.000336), (b"if ", 0.000336), (b"w, ", 0.000336),
+    (b"en,", 0.000334), (b"cam", 0.000334), (b" af", 0.000334), (b" Sh", 0.000332),
+    (b"ile", 0.000332), (b" im", 0.000332), (b"m. ", 0.000332), (b"oss", 0.000332),
+    (b"orn", 0.000332), (b"re,", 0.000332), (b" if", 0.000332), (b"g, ", 0.000329),
+    (b"me,", 0.000329), (b"dre", 0.000329), (b"efo", 0.000329), (b"ser", 0.000327),
+    (b"ple", 0.000327), (b" ac", 0.000325), (b"But", 0.000325), (b"t u", 0.000325),
+    (b"eri", 0.000325), (b"g i", 0.000325), (b"dis", 0.000323), (b"cti", 0.000322),
+    (b"lar", 0.00032), (b"cle", 0.000318), (b"spe", 0.000318), (b" ga", 0.000318),
+    (b"dea", 0.000318), (b"oin", 0.000316), (b"sse", 0.000316), (b"d p", 0.000314),
+    (b"s r", 0.000314), (b"mus", 0.000314), (b" bl", 0.000314), (b"o y", 0.000314),
+    (b"imp", 0.000314), (b"ret", 0.000313), (b"car", 0.000313), (b"d H", 0.000313),
+    (b"ok ", 0.000313), (b"hem", 0.000311), (b"oke", 0.000311), (b"eth", 0.000311),
+    (b"ond", 0.000309), (b"rni", 0.000309), (b"o c", 0.000309), (b"s I", 0.000309),
+    (b"od ", 0.000309), (b"ow,", 0.000309), (b"n e", 0.000307), (b"inc", 0.000307),
+    (b"u h", 0.000307), (b"ng.", 0.000307), (b"t l", 0.000306), (b"fou", 0.000306),
+    (b" ot", 0.000304), (b"ert", 0.000304), (b"bac", 0.000304), (b"f m", 0.000302),
+    (b"u w", 0.000302), (b"med", 0.000302), (b"t p", 0.0003), (b"g h", 0.0003), (b"n b", 0.0003),
+    (b".' ", 0.0003), (b"sen", 0.000298), (b"bef", 0.000298), (b"r c", 0.000298),
+    (b"ett", 0.000297), (b" hu", 0.000297), (b"sio", 0.000295), (b"o, ", 0.000295),
+    (b"ont", 0.000295), (b" ea", 0.000293), (b"cri", 0.000293), (b", M", 0.000293),
+    (b"ndo", 0.000291), (b" sm", 0.000291), (b"dy ", 0.000289), (b"n I", 0.000289),
+    (b" op", 0.000289), (b" gl", 0.000288), (b"a f", 0.000286), (b"onl", 0.000286),
+    (b"ns ", 0.000284), (b"omp", 0.000284), (b"rig", 0.000284), (b" ad", 0.000282),
+    (b"lac", 0.000282), (b". B", 0.000282), (b"d r", 0.000282), (b"ful", 0.000282),
+    (b"ger", 0.000282), (b"me.", 0.000281), (b"par", 0.000281), (b"o i", 0.000281),
+    (b"nst", 0.000281), (b"doo", 0.000281), (b"did", 0.000281), (b"it.", 0.000281),
+    (b"ady", 0.000281), (b"iti", 0.000279), (b"e v", 0.000279), (b"lic", 0.000279),
+    (b"eed", 0.000279), (b"erl", 0.000277), (b"fir", 0.000277), (b"las", 0.000277),
+    (b"ndi", 0.000277), (b"tic", 0.000275), (b"f i", 0.000275), (b"fte", 0.000275),
+    (b"sur", 0.000275), (b"ies", 0.000273), (b"llo", 0.000273), (b" Yo", 0.000273),
+    (b"s.\"", 0.000273), (b" sl", 0.000273), (b"vin", 0.000272), (b"hro", 0.000272),
+    (b"clo", 0.000272), (b"goo", 0.000272), (b" na", 0.00027), (b"rst", 0.00027),
+    (b"a w", 0.00027), (b"lig", 0.00027), (b"met", 0.00027), (b"tak", 0.000268),
+    (b"ce,", 0.000266), (b"off", 0.000264), (b"bro", 0.000264), (b"ike", 0.000264),
+    (b"cal", 0.000264), (b"ask", 0.000264), (b"war", 0.000264), (b"len", 0.000263),
+];
@@ -0,0 +1,117 @@
+use std::ops;
+use std::collections::HashMap;
+
+use util::*;
+
+/// a candidate repeating key length. higher scores are more likely, but
+/// scores are only comparable within the same method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeySize {
+    pub length: usize,
+    pub score: f64,
+}
+
+// best first; equal scores prefer the shorter key.
+fn rank(mut candidates: Vec<KeySize>) -> Vec<KeySize> {
+    candidates.sort_by(|a, b| best_first(a.score, b.score).then(a.length.cmp(&b.length)));
+    candidates
+}
+
+/// the hamming distance between every pair of full key-sized blocks, in bits
+/// per byte and negated. lengths with fewer than two full blocks are skipped.
+pub fn hamming_keysizes(v: &[u8], range: ops::Range<usize>) -> Vec<KeySize> {
+    let mut candidates = vec![];
+    for length in range {
+        if length == 0 {
+            continue;
+        }
+        let blocks: Vec<&[u8]> = v.chunks(length).filter(|c| c.len() == length).collect();
+        if blocks.len() < 2 {
+            continue;
+        }
+        let mut total = 0;
+        let mut pairs = 0;
+        for i in 0..blocks.len() {
+            for j in i + 1..blocks.len() {
+                total += hamming(blocks[i], blocks[j]);
+                pairs += 1;
+            }
+        }
+        let normalized = total as f64 / pairs as f64 / length as f64;
+        candidates.push(KeySize { length: length, score: -normalized });
+    }
+    rank(candidates)
+}
+
+/// the index of coincidence of each column of bytes encrypted under the same
+/// key byte, averaged over the columns.
+pub fn coincidence_keysizes(v: &[u8], range: ops::Range<usize>) -> Vec<KeySize> {
+    let mut candidates = vec![];
+    for length in range {
+        if length == 0 {
+            continue;
+        }
+        let mut total = 0.0;
+        let mut columns = 0;
+        for column in 0..length {
+            let mut counts = [0usize; 256];
+            let mut n = 0;
+            let mut i = column;
+            while i < v.len() {
+                counts[v[i] as usize] += 1;
+                n += 1;
+                i += length;
+            }
+            if n < 2 {
+                continue;
+            }
+            let coincidences = counts.iter().fold(0, |accum, &c| accum + c * c.saturating_sub(1));
+            total += coincidences as f64 / (n * (n - 1)) as f64;
+            columns += 1;
+        }
+        if columns > 0 {
+            candidates.push(KeySize { length: length, score: total / columns as f64 });
+        }
+    }
+    rank(candidates)
+}
+
+/// the fraction of bytes equal to the byte `length` positions later.
+pub fn autocorrelation_keysizes(v: &[u8], range: ops::Range<usize>) -> Vec<KeySize> {
+    let mut candidates = vec![];
+    for length in range {
+        if length == 0 || length >= v.len() {
+            continue;
+        }
+        let matches = v.iter().zip(&v[length..]).filter(|&(x, y)| x == y).count();
+        let score = matches as f64 / (v.len() - length) as f64;
+        candidates.push(KeySize { length: length, score: score });
+    }
+    rank(candidates)
+}
+
+/// Kasiski examination: how often the distances between repeated trigrams are
+/// a multiple of each length, relative to chance.
+pub fn kasiski_keysizes(v: &[u8], range: ops::Range<usize>) -> Vec<KeySize> {
+    let mut last_seen: HashMap<&[u8], usize> = HashMap::new();
+    let mut distances = vec![];
+    for (i, w) in v.windows(3).enumerate() {
+        if let Some(previous) = last_seen.insert(w, i) {
+            distances.push(i - previous);
+        }
+    }
+    let mut candidates = vec![];
+    if distances.len() == 0 {
+        return candidates;
+    }
+    for length in range {
+        if length == 0 {
+            continue;
+        }
+        // a random distance is a multiple of `length` one time in `length`.
+        let multiples = distances.iter().filter(|&&d| d % length == 0).count();
+        let score = multiples as f64 / distances.len() as f64 * length as f64;
+        candidates.push(KeySize { length: length, score: score });
+    }
+    rank(candidates)
+}
@@ -0,0 +1,132 @@
+use std::{error, fmt};
+
+use crypto::*;
+use score::*;
+use util::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CribError {
+    /// there is no message with this index.
+    NoSuchMessage(usize),
+    /// the crib runs past the end of the message, which is this long.
+    TooLong(usize),
+}
+
+impl fmt::Display for CribError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CribError::NoSuchMessage(n) => write!(f, "no message {}", n),
+            CribError::TooLong(n) => write!(f, "crib runs past the end of a {} byte message", n),
+        }
+    }
+}
+
+impl error::Error for CribError {
+    fn description(&self) -> &str {
+        match *self {
+            CribError::NoSuchMessage(..) => "no such message",
+            CribError::TooLong(..) => "crib too long",
+        }
+    }
+}
+
+/// breaks a set of ciphertexts that were all xor'd with the same keystream,
+/// as with a reused one-time pad or CTR under a fixed nonce.
+///
+/// `solve` guesses the keystream a column at a time, and `pin` fixes part of
+/// it from known plaintext in one message so every other message can be read
+/// at that position. pinned bytes are never overwritten by `solve`.
+pub struct ManyTimePad {
+    ciphertexts: Vec<Vec<u8>>,
+    keystream: Vec<u8>,
+    pinned: Vec<bool>,
+}
+
+impl ManyTimePad {
+    pub fn new(ciphertexts: Vec<Vec<u8>>) -> ManyTimePad {
+        let length = ciphertexts.iter().map(|c| c.len()).max().unwrap_or(0);
+        ManyTimePad {
+            ciphertexts: ciphertexts,
+            keystream: vec![0; length],
+            pinned: vec![false; length],
+        }
+    }
+
+    /// pick the most plausible keystream byte for every column that isn't
+    /// pinned. columns past the end of most messages have little to go on.
+    pub fn solve(&mut self, scorer: &Scorer) {
+        let columns = transpose(&self.ciphertexts);
+        for (i, column) in columns.iter().enumerate() {
+            if !self.pinned[i] {
+                self.keystream[i] = single_byte_xor(column, scorer, 1)[0].key;
+            }
+        }
+    }
+
+    /// fix the keystream so that `message` reads `crib` at `offset`.
+    pub fn pin(&mut self, message: usize, offset: usize, crib: &[u8]) -> Result<(), CribError> {
+        let ciphertext = match self.ciphertexts.get(message) {
+            Some(c) => c,
+            None => return Err(CribError::NoSuchMessage(message))
+        };
+        if offset + crib.len() > ciphertext.len() {
+            return Err(CribError::TooLong(ciphertext.len()));
+        }
+        for (i, &x) in crib.iter().enumerate() {
+            self.keystream[offset + i] = ciphertext[offset + i] ^ x;
+            self.pinned[offset + i] = true;
+        }
+        Ok(())
+    }
+
+    /// let `solve` guess these keystream bytes again. anything past the end
+    /// of the keystream is ignored.
+    pub fn unpin(&mut self, offset: usize, length: usize) {
+        let end = ::std::cmp::min(offset.saturating_add(length), self.pinned.len());
+        for i in offset..end {
+            self.pinned[i] = false;
+        }
+    }
+
+    pub fn keystream(&self) -> &[u8] {
+        &self.keystream
+    }
+
+    pub fn is_pinned(&self, offset: usize) -> bool {
+        self.pinned.get(offset).cloned().unwrap_or(false)
+    }
+
+    pub fn plaintext(&self, message: usize) -> Option<Vec<u8>> {
+        self.ciphertexts.get(message).map(|c| xor(c, &self.keystream))
+    }
+
+    pub fn plaintexts(&self) -> Vec<Vec<u8>> {
+        self.ciphertexts.iter().map(|c| xor(c, &self.keystream)).collect()
+    }
+}
+
+/// one placement of a crib dragged across two ciphertexts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CribMatch {
+    pub offset: usize,
+    /// what the other plaintext reads at `offset` if the crib belongs there.
+    pub fragment: Vec<u8>,
+    pub score: f64,
+}
+
+/// slides `crib` across every offset of two ciphertexts that share a
+/// keystream. the keystream cancels out, so wherever the crib is right the
+/// other plaintext shows through. best first; ties go to the earlier offset.
+pub fn crib_drag(a: &Vec<u8>, b: &Vec<u8>, crib: &[u8], scorer: &Scorer) -> Vec<CribMatch> {
+    let combined = xor(a, b);
+    if crib.len() == 0 || crib.len() > combined.len() {
+        return vec![];
+    }
+    let mut result: Vec<CribMatch> = combined.windows(crib.len()).enumerate().map(|(offset, w)| {
+        let fragment: Vec<u8> = w.iter().zip(crib).map(|(x, y)| x ^ y).collect();
+        let score = scorer.score(&fragment);
+        CribMatch { offset: offset, fragment: fragment, score: score }
+    }).collect();
+    result.sort_by(|a, b| best_first(a.score, b.score).then(a.offset.cmp(&b.offset)));
+    result
+}
@@ -1,16 +1,32 @@
-#![feature(str_char)]
 #![allow(dead_code)]
 #![allow(unused_imports)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
 extern crate quickcheck;
+#[cfg(feature = "openssl")]
 extern crate openssl;
 extern crate rand;
+#[cfg(feature = "nightly")]
+extern crate test;
 
 mod util;
+mod score;
+mod frequency;
+mod aes;
+mod cipher;
 mod crypto;
+mod keysize;
+mod keystream;
 mod conversion;
+mod stream;
+mod blocks;
+mod padding;
+mod form;
 mod set1;
 mod set2;
+mod set3;
+#[cfg(all(test, feature = "nightly"))]
+mod bench;
 
 fn main() {
     println!("Nothing to see here.");
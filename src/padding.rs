@@ -0,0 +1,189 @@
+use std::{error, fmt};
+use rand::{thread_rng, Rng};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaddingError {
+    /// the input isn't a non-empty whole number of blocks, or the block size
+    /// is zero; this is its length.
+    BadLength(usize),
+    /// the final byte claims this many bytes of padding, which is zero or
+    /// more than a block.
+    BadPadLength(u8),
+    /// the padding byte at this offset doesn't match the scheme.
+    BadByte(usize),
+    /// no marker byte was found in the final block.
+    MissingMarker,
+}
+
+impl fmt::Display for PaddingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PaddingError::BadLength(n) => write!(f, "{} bytes is not a whole number of blocks", n),
+            PaddingError::BadPadLength(n) => write!(f, "invalid padding length {}", n),
+            PaddingError::BadByte(i) => write!(f, "invalid padding byte at offset {}", i),
+            PaddingError::MissingMarker => write!(f, "no padding marker in the final block"),
+        }
+    }
+}
+
+impl error::Error for PaddingError {
+    fn description(&self) -> &str {
+        match *self {
+            PaddingError::BadLength(..) => "not a whole number of blocks",
+            PaddingError::BadPadLength(..) => "invalid padding length",
+            PaddingError::BadByte(..) => "invalid padding byte",
+            PaddingError::MissingMarker => "missing padding marker",
+        }
+    }
+}
+
+/// a way of padding data out to a whole number of blocks.
+///
+/// `pad` always adds at least one byte, so a full block of padding follows
+/// input that is already aligned. the exception is `ZeroPadding`, which can't
+/// be removed reliably anyway.
+pub trait Padding {
+    fn pad(&self, v: &[u8], block_size: usize) -> Vec<u8>;
+
+    /// the input without its padding, or why the padding is invalid.
+    fn unpad<'a>(&self, v: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError>;
+}
+
+/// PKCS#7: n bytes of value n.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pkcs7;
+
+/// ANSI X.923: zeros followed by a byte giving the padding length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnsiX923;
+
+/// ISO/IEC 7816-4: a 0x80 marker followed by zeros.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Iso7816;
+
+/// ISO 10126: random bytes followed by a byte giving the padding length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Iso10126;
+
+/// zeros up to the end of the block, and nothing for aligned input. unpadding
+/// strips every trailing zero, including any that belonged to the data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZeroPadding;
+
+fn pad_length(v: &[u8], block_size: usize) -> usize {
+    block_size - v.len() % block_size
+}
+
+fn check_length(v: &[u8], block_size: usize) -> Result<(), PaddingError> {
+    if block_size == 0 || v.len() == 0 || v.len() % block_size != 0 {
+        return Err(PaddingError::BadLength(v.len()));
+    }
+    Ok(())
+}
+
+// the padding length from the final byte, for the schemes that store it.
+fn stored_length(v: &[u8], block_size: usize) -> Result<usize, PaddingError> {
+    try!(check_length(v, block_size));
+    let n = v[v.len() - 1];
+    if n == 0 || n as usize > block_size {
+        return Err(PaddingError::BadPadLength(n));
+    }
+    Ok(n as usize)
+}
+
+// `filler` is given the padding length. the length byte has to fit the
+// whole padding, so blocks are at most 255 bytes.
+fn with_length_byte(v: &[u8], block_size: usize, filler: &mut FnMut(usize) -> u8) -> Vec<u8> {
+    assert!(block_size > 0 && block_size < 256);
+    let n = pad_length(v, block_size);
+    let mut result = v.to_vec();
+    for _ in 1..n {
+        result.push(filler(n));
+    }
+    result.push(n as u8);
+    result
+}
+
+impl Padding for Pkcs7 {
+    fn pad(&self, v: &[u8], block_size: usize) -> Vec<u8> {
+        with_length_byte(v, block_size, &mut |n| n as u8)
+    }
+
+    fn unpad<'a>(&self, v: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        let n = try!(stored_length(v, block_size));
+        let start = v.len() - n;
+        match v[start..].iter().position(|&x| x != n as u8) {
+            Some(i) => Err(PaddingError::BadByte(start + i)),
+            None => Ok(&v[..start])
+        }
+    }
+}
+
+impl Padding for AnsiX923 {
+    fn pad(&self, v: &[u8], block_size: usize) -> Vec<u8> {
+        with_length_byte(v, block_size, &mut |_| 0)
+    }
+
+    fn unpad<'a>(&self, v: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        let n = try!(stored_length(v, block_size));
+        let start = v.len() - n;
+        match v[start..v.len() - 1].iter().position(|&x| x != 0) {
+            Some(i) => Err(PaddingError::BadByte(start + i)),
+            None => Ok(&v[..start])
+        }
+    }
+}
+
+impl Padding for Iso7816 {
+    fn pad(&self, v: &[u8], block_size: usize) -> Vec<u8> {
+        assert!(block_size > 0);
+        let n = pad_length(v, block_size);
+        let mut result = v.to_vec();
+        result.push(0x80);
+        result.extend(::std::iter::repeat(0).take(n - 1));
+        result
+    }
+
+    fn unpad<'a>(&self, v: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        try!(check_length(v, block_size));
+        let block_start = v.len() - block_size;
+        for i in (block_start..v.len()).rev() {
+            match v[i] {
+                0 => continue,
+                0x80 => return Ok(&v[..i]),
+                _ => return Err(PaddingError::BadByte(i))
+            }
+        }
+        Err(PaddingError::MissingMarker)
+    }
+}
+
+impl Padding for Iso10126 {
+    fn pad(&self, v: &[u8], block_size: usize) -> Vec<u8> {
+        let mut rng = thread_rng();
+        with_length_byte(v, block_size, &mut |_| rng.gen::<u8>())
+    }
+
+    fn unpad<'a>(&self, v: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        let n = try!(stored_length(v, block_size));
+        Ok(&v[..v.len() - n])
+    }
+}
+
+impl Padding for ZeroPadding {
+    fn pad(&self, v: &[u8], block_size: usize) -> Vec<u8> {
+        assert!(block_size > 0);
+        let n = (block_size - v.len() % block_size) % block_size;
+        let mut result = v.to_vec();
+        result.extend(::std::iter::repeat(0).take(n));
+        result
+    }
+
+    fn unpad<'a>(&self, v: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        if block_size == 0 || v.len() % block_size != 0 {
+            return Err(PaddingError::BadLength(v.len()));
+        }
+        let end = v.iter().rposition(|&x| x != 0).map(|i| i + 1).unwrap_or(0);
+        Ok(&v[..end])
+    }
+}
@@ -0,0 +1,223 @@
+use std::io::prelude::*;
+use std::io::{self, BufReader};
+use std::fs::File;
+use std::collections::HashMap;
+
+use conversion::*;
+use frequency::*;
+
+/// rates how plausible a candidate plaintext is. higher is better, and scores
+/// are normalised by length so inputs of different sizes can be compared.
+pub trait Scorer {
+    fn score(&self, v: &[u8]) -> f64;
+}
+
+pub fn is_text(x: u8) -> bool {
+    x == b'\t' || x == b'\n' || x == b'\r' || (x >= 0x20 && x < 0x7f)
+}
+
+// the frequency tables treat any whitespace as a plain space.
+fn fold_whitespace(x: u8) -> u8 {
+    match x {
+        b'\t' | b'\n' | b'\r' | 0x0b | 0x0c => b' ',
+        _ => x
+    }
+}
+
+/// the fraction of bytes that are printable ascii or common whitespace.
+pub struct PrintableRatio;
+
+impl Scorer for PrintableRatio {
+    fn score(&self, v: &[u8]) -> f64 {
+        if v.len() == 0 {
+            return 0.0;
+        }
+        v.iter().filter(|&&x| is_text(x)).count() as f64 / v.len() as f64
+    }
+}
+
+/// Pearson's chi-squared statistic against expected byte frequencies, negated
+/// so that a closer fit scores higher.
+pub struct ChiSquared {
+    expected: HashMap<u8, f64>,
+    // share of everything the table doesn't list.
+    other: f64,
+}
+
+impl ChiSquared {
+    /// uses the single byte entries of a frequency table.
+    pub fn new(table: &[(&[u8], f64)]) -> ChiSquared {
+        let expected: HashMap<u8, f64> = table.iter().filter(|&&(k, _)| k.len() == 1).map(|&(k, p)| {
+            (k[0], p)
+        }).collect();
+        let total: f64 = expected.values().fold(0.0, |accum, &p| accum + p);
+        ChiSquared {
+            expected: expected,
+            other: (1.0 - total).max(1e-6),
+        }
+    }
+
+    pub fn english() -> ChiSquared {
+        ChiSquared::new(ENGLISH_UNIGRAMS)
+    }
+}
+
+impl Scorer for ChiSquared {
+    fn score(&self, v: &[u8]) -> f64 {
+        if v.len() == 0 {
+            return 0.0;
+        }
+        let n = v.len() as f64;
+        let mut observed = HashMap::new();
+        let mut other = 0;
+        for &x in v {
+            let x = fold_whitespace(x);
+            if self.expected.contains_key(&x) {
+                *observed.entry(x).or_insert(0) += 1;
+            } else {
+                other += 1;
+            }
+        }
+        let deviation = |o: f64, e: f64| (o - e) * (o - e) / e;
+        let mut chi = deviation(other as f64, self.other * n);
+        for (x, &p) in self.expected.iter() {
+            let o = *observed.get(x).unwrap_or(&0) as f64;
+            chi += deviation(o, p * n);
+        }
+        -chi / n
+    }
+}
+
+/// the mean log10 probability of each overlapping n-gram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NgramModel {
+    n: usize,
+    log_probs: HashMap<Vec<u8>, f64>,
+    // charged for any n-gram the model has never seen.
+    floor: f64,
+}
+
+impl NgramModel {
+    /// uses the entries of a frequency table that have length `n`.
+    pub fn from_table(n: usize, table: &[(&[u8], f64)]) -> NgramModel {
+        assert!(n > 0);
+        let log_probs: HashMap<Vec<u8>, f64> = table.iter().filter(|&&(k, p)| {
+            k.len() == n && p > 0.0
+        }).map(|&(k, p)| (k.to_vec(), p.log10())).collect();
+        // an order of magnitude below the rarest n-gram we know about.
+        let rarest = log_probs.values().fold(0.0f64, |accum, &p| accum.min(p));
+        NgramModel {
+            n: n,
+            log_probs: log_probs,
+            floor: rarest - 1.0,
+        }
+    }
+
+    /// counts every overlapping n-gram of a corpus, with whitespace folded the
+    /// same way as when scoring.
+    pub fn train(corpus: &[u8], n: usize) -> NgramModel {
+        assert!(n > 0);
+        let folded: Vec<u8> = corpus.iter().map(|&x| fold_whitespace(x)).collect();
+        let mut counts: HashMap<&[u8], usize> = HashMap::new();
+        for w in folded.windows(n) {
+            *counts.entry(w).or_insert(0) += 1;
+        }
+        let total = counts.values().fold(0, |accum, &c| accum + c) as f64;
+        let table: Vec<(&[u8], f64)> = counts.iter().map(|(&k, &c)| (k, c as f64 / total)).collect();
+        NgramModel::from_table(n, &table)
+    }
+
+    pub fn train_file(filename: &str, n: usize) -> io::Result<NgramModel> {
+        let mut f = try!(File::open(filename));
+        let mut corpus = vec![];
+        try!(f.read_to_end(&mut corpus));
+        Ok(NgramModel::train(&corpus, n))
+    }
+
+    /// writes a header line of the order and floor, then one hex encoded
+    /// n-gram and its log probability per line.
+    pub fn save<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        try!(writeln!(w, "{} {}", self.n, self.floor));
+        // sorted so that the same model always serializes the same way.
+        let mut entries: Vec<(&Vec<u8>, &f64)> = self.log_probs.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        for (k, p) in entries {
+            try!(writeln!(w, "{} {}", HEX_LOWER.encode(k), p));
+        }
+        Ok(())
+    }
+
+    pub fn load<R: Read>(r: R) -> io::Result<NgramModel> {
+        let invalid = |line: usize, what: &str| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", line + 1, what))
+        };
+        let mut lines = BufReader::new(r).lines().enumerate();
+        let header = match lines.next() {
+            Some((_, line)) => try!(line),
+            None => return Err(invalid(0, "missing header"))
+        };
+        let fields: Vec<&str> = header.split(' ').collect();
+        if fields.len() != 2 {
+            return Err(invalid(0, "expected order and floor"));
+        }
+        let n = try!(fields[0].parse::<usize>().map_err(|_| invalid(0, "bad order")));
+        if n == 0 {
+            return Err(invalid(0, "order must be at least 1"));
+        }
+        let floor = try!(fields[1].parse::<f64>().map_err(|_| invalid(0, "bad floor")));
+
+        let mut log_probs = HashMap::new();
+        for (i, line) in lines {
+            let line = try!(line);
+            let fields: Vec<&str> = line.split(' ').collect();
+            if fields.len() != 2 {
+                return Err(invalid(i, "expected n-gram and log probability"));
+            }
+            let Hex(k) = try!(decode_hex(fields[0]).map_err(|e| invalid(i, &e.to_string())));
+            if k.len() != n {
+                return Err(invalid(i, "n-gram has the wrong length"));
+            }
+            let p = try!(fields[1].parse::<f64>().map_err(|_| invalid(i, "bad log probability")));
+            log_probs.insert(k, p);
+        }
+        Ok(NgramModel { n: n, log_probs: log_probs, floor: floor })
+    }
+
+    pub fn save_file(&self, filename: &str) -> io::Result<()> {
+        let mut f = try!(File::create(filename));
+        self.save(&mut f)
+    }
+
+    pub fn load_file(filename: &str) -> io::Result<NgramModel> {
+        NgramModel::load(try!(File::open(filename)))
+    }
+
+    /// unigram, bigram or trigram English.
+    pub fn english(n: usize) -> NgramModel {
+        match n {
+            1 => NgramModel::from_table(1, ENGLISH_UNIGRAMS),
+            2 => NgramModel::from_table(2, ENGLISH_BIGRAMS),
+            3 => NgramModel::from_table(3, ENGLISH_TRIGRAMS),
+            _ => panic!("no bundled English table for {}-grams", n)
+        }
+    }
+
+    pub fn order(&self) -> usize {
+        self.n
+    }
+}
+
+impl Scorer for NgramModel {
+    fn score(&self, v: &[u8]) -> f64 {
+        let folded: Vec<u8> = v.iter().map(|&x| fold_whitespace(x)).collect();
+        if folded.len() < self.n {
+            return self.floor;
+        }
+        let windows = folded.windows(self.n);
+        let count = windows.len();
+        let total = windows.fold(0.0, |accum, w| {
+            accum + *self.log_probs.get(w).unwrap_or(&self.floor)
+        });
+        total / count as f64
+    }
+}
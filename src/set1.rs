@@ -1,41 +1,115 @@
 
+use std::io::prelude::*;
+use std::io;
+use std::io::Cursor;
+use std::fs::File;
 use quickcheck::{quickcheck, Gen, Arbitrary};
+use rand::{thread_rng, Rng};
 
 use conversion::*;
 use crypto::*;
+use keysize::*;
+use keystream::*;
+use score::*;
+use stream::*;
 use util::*;
 
 #[test]
 fn challenge_1() {
     let input = "49276d206b696c6c696e6720796f757220627261696e206c696b65206120706f69736f6e6f7573206d757368726f6f6d";
-    let Hex(v) = string_to_hex(input);
-    let result = base64_to_string(raw_to_base64(&v));
+    let hex: Hex = input.parse().unwrap();
+    let result = Base64::from(hex).to_string();
     assert!(result == "SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t");
 }
 
 #[test]
 fn challenge_2() {
-    let Hex(x) = string_to_hex("1c0111001f010100061a024b53535009181c");
-    let Hex(y) = string_to_hex("686974207468652062756c6c277320657965");
-    let result = hex_to_string(Hex(xor(&x, &y)));
-    assert!(result == "746865206B696420646F6E277420706C6179");
+    let Hex(x) = "1c0111001f010100061a024b53535009181c".parse().unwrap();
+    let Hex(y) = "686974207468652062756c6c277320657965".parse().unwrap();
+    let result = Hex::from(xor(&x, &y));
+    assert!(result == "746865206b696420646f6e277420706c6179");
 }
 
 #[test]
 fn challenge_3() {
     let encrypted = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
-    let Hex(v) = string_to_hex(encrypted);
-    let result = single_byte_xor(&v);
-    let best_match = result.iter().next().unwrap();
-    assert!(&best_match.1[..] == "Cooking MC's like a pound of bacon");
+    let Hex(v) = encrypted.parse().unwrap();
+    let result = single_byte_xor(&v, &NgramModel::english(2), 5);
+    assert!(result.len() == 5);
+    assert!(result.windows(2).all(|w| w[0].score >= w[1].score));
+    let best_match = &result[0];
+    assert!(best_match.plaintext == b"Cooking MC's like a pound of bacon".to_vec());
+    assert!(best_match.key == b'X');
 }
 
 #[test]
 fn challenge_4() {
     let tests = read_hexlines_file("data/4.txt");
-    let result = detect_single_byte_xor(&tests);
-    let best_match = result.iter().next().unwrap();
-    assert!(&best_match.1[..] == "Now that the party is jumping\n");
+    let result = detect_single_byte_xor(&tests, &NgramModel::english(2), 3);
+    assert!(result.len() == 3);
+    let best_match = &result[0];
+    assert!(best_match.plaintext == b"Now that the party is jumping\n".to_vec());
+    assert!(xor_one(&tests[best_match.index], best_match.key) == best_match.plaintext);
+}
+
+#[test]
+fn scorers_agree_on_challenges() {
+    let scorers: Vec<Box<Scorer>> = vec![Box::new(ChiSquared::english()),
+                                         Box::new(NgramModel::english(1)),
+                                         Box::new(NgramModel::english(2)),
+                                         Box::new(NgramModel::english(3))];
+    let Hex(v) = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736".parse().unwrap();
+    let tests = read_hexlines_file("data/4.txt");
+    let Base64(block) = read_base64_file("data/6.txt");
+    for scorer in scorers.iter() {
+        let result = single_byte_xor(&v, &**scorer, 1);
+        assert!(result[0].plaintext == b"Cooking MC's like a pound of bacon".to_vec());
+        let result = detect_single_byte_xor(&tests, &**scorer, 1);
+        assert!(result[0].plaintext == b"Now that the party is jumping\n".to_vec());
+        let keys = break_repeating_key_xor(&block, &vec![29], &**scorer);
+        assert!(keys[0] == "Terminator X: Bring the noise");
+    }
+}
+
+#[test]
+fn printable_ratio() {
+    assert!(PrintableRatio.score(b"hello,\tworld\n") == 1.0);
+    assert!(PrintableRatio.score(&[b'a', 0x00, 0xff, b'b']) == 0.5);
+    assert!(NgramModel::english(2).score(b"the cat sat") > NgramModel::english(2).score(b"xq zvk jjq"));
+    assert!(ChiSquared::english().score(b"the cat sat") > ChiSquared::english().score(b"\x01\x02\x03"));
+}
+
+#[test]
+fn trained_ngram_model() {
+    let corpus = b"{\"level\":\"info\",\"msg\":\"request served\",\"status\":200}\n\
+                   {\"level\":\"warn\",\"msg\":\"slow request\",\"status\":200}\n\
+                   {\"level\":\"error\",\"msg\":\"upstream failed\",\"status\":502}\n";
+    let model = NgramModel::train(corpus, 3);
+    assert!(model.order() == 3);
+
+    let plaintext = b"{\"level\":\"info\",\"msg\":\"cache miss\",\"status\":404}";
+    let encrypted = xor_one(plaintext, 0x5a);
+    let result = single_byte_xor(&encrypted, &model, 1);
+    assert!(result[0].plaintext == plaintext.to_vec() && result[0].key == 0x5a);
+
+    let mut saved = vec![];
+    model.save(&mut saved).unwrap();
+    let loaded = NgramModel::load(Cursor::new(&saved)).unwrap();
+    assert!(loaded == model);
+    assert!(loaded.score(plaintext) == model.score(plaintext));
+
+    let path = ::std::env::temp_dir().join("matasano-trained-ngram-model.txt");
+    let path = path.to_str().unwrap();
+    model.save_file(path).unwrap();
+    assert!(NgramModel::load_file(path).unwrap() == model);
+    let _ = ::std::fs::remove_file(path);
+
+    assert!(NgramModel::load(Cursor::new("3\n")).is_err());
+    assert!(NgramModel::load(Cursor::new("3 -5\n7468 -1.0\n")).is_err());
+    assert!(NgramModel::load(Cursor::new("2 -5\n746865 -1.0\n")).is_err());
+    let e = NgramModel::load(Cursor::new("0 -5\n")).unwrap_err();
+    assert!(e.kind() == io::ErrorKind::InvalidData);
+    assert!(NgramModel::train_file("data/missing.txt", 2).is_err());
 }
 
 #[test]
@@ -44,18 +118,100 @@ fn challenge_5() {
 I go crazy when I hear a cymbal";
     let key = "ICE";
 
-    let o = string_to_raw(&original);
-    let result = xor_key(&o, &key);
-    let encrypted = hex_to_string(Hex(result));
+    let o = string_to_raw(&original).unwrap();
+    let result = xor_key(&o, &key).unwrap();
+    let encrypted = format!("{:x}", Hex(result));
+
+    assert!(encrypted == "0b3637272a2b2e63622c2e69692a23693a2a3c6324202d623d63343c2a26226324272765272a282b2f20430a652e2c652a3124333a653e2b2027630c692b20283165286326302e27282f");
 
-    assert!(encrypted == "0B3637272A2B2E63622C2E69692A23693A2A3C6324202D623D63343C2A26226324272765272A282B2F20430A652E2C652A3124333A653E2B2027630C692B20283165286326302E27282F")
+    // keys are Latin-1, so anything past U+00FF is an error rather than a panic.
+    assert!(xor_key(&o, "\u{e9}").unwrap() == xor_one(&o, 0xe9));
+    assert!(xor_key(&o, "I\u{263a}") == Err(DecodeError::InvalidCharacter('\u{263a}', 1)));
+}
+
+#[test]
+fn keysize_methods() {
+    let Base64(block) = read_base64_file("data/6.txt");
+    let methods: Vec<fn(&[u8], ::std::ops::Range<usize>) -> Vec<KeySize>> =
+        vec![hamming_keysizes, coincidence_keysizes, autocorrelation_keysizes, kasiski_keysizes];
+    for method in methods.iter() {
+        let candidates = method(&block, 2..41);
+        assert!(candidates[0].length == 29);
+        assert!(candidates.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    // far too short for four blocks of every length.
+    let short = xor_key(&string_to_raw("a short message, really").unwrap(), "KEY").unwrap();
+    assert!(likely_keysizes(&short, 2..41).len() > 0);
+    assert!(hamming_keysizes(&short, 2..41).iter().all(|k| k.length <= short.len() / 2));
+    assert!(autocorrelation_keysizes(&[], 1..10).is_empty());
+    assert!(kasiski_keysizes(&[1, 2], 1..10).is_empty());
 }
 
 #[test]
 fn hamming_distance() {
-    let phrase1 = string_to_raw("this is a test");
-    let phrase2 = string_to_raw("wokka wokka!!!");
+    let phrase1 = string_to_raw("this is a test").unwrap();
+    let phrase2 = string_to_raw("wokka wokka!!!").unwrap();
     assert!(hamming(&phrase1, &phrase2) == 37);
+    assert!(hamming(b"", b"") == 0);
+    // two whole words and a remainder.
+    assert!(hamming(&[0xff; 19], &[0; 19]) == 19 * 8);
+}
+
+#[test]
+fn hamming_counts_every_bit() {
+    fn matches_bytewise_count(pairs: Vec<(u8, u8)>) -> bool {
+        let a: Vec<u8> = pairs.iter().map(|p| p.0).collect();
+        let b: Vec<u8> = pairs.iter().map(|p| p.1).collect();
+        let expected = pairs.iter().fold(0, |accum, &(x, y)| accum + (x ^ y).count_ones() as usize);
+        hamming(&a, &b) == expected
+    }
+    quickcheck(matches_bytewise_count as fn(Vec<(u8, u8)>) -> bool);
+}
+
+#[test]
+#[should_panic]
+fn hamming_rejects_mismatched_lengths() {
+    hamming(b"abc", b"ab");
+}
+
+#[test]
+fn xor_in_place() {
+    let mut v = b"hello".to_vec();
+    xor_into(&mut v, &[1, 2, 3, 4, 5]);
+    assert!(v == xor(b"hello", &[1, 2, 3, 4, 5]));
+    xor_into(&mut v, &[1, 2, 3, 4, 5]);
+    assert!(v == b"hello".to_vec());
+
+    // xor truncates, xor_checked refuses and xor_into panics.
+    assert!(xor(b"hello", b"he") == vec![0, 0]);
+    assert!(xor_checked(b"hello", b"he") == None);
+    assert!(xor_checked(b"hello", b"hello") == Some(vec![0; 5]));
+
+    fn cycle_matches_key(v: Vec<u8>, key: Vec<u8>) -> bool {
+        if key.len() == 0 {
+            return true;
+        }
+        let mut result = v.clone();
+        xor_cycle(&mut result, &key);
+        let expected: Vec<u8> = v.iter().zip(key.iter().cycle()).map(|(x, y)| x ^ y).collect();
+        result == expected && xor_key_bytes(&v, &key) == expected
+    }
+    quickcheck(cycle_matches_key as fn(Vec<u8>, Vec<u8>) -> bool);
+
+    // an empty key changes nothing, however it's given.
+    let mut v = b"hello".to_vec();
+    xor_cycle(&mut v, &[]);
+    assert!(v == b"hello".to_vec());
+    assert!(xor_key_bytes(b"hello", &[]) == b"hello".to_vec());
+    assert!(xor_key(b"hello", "") == Ok(b"hello".to_vec()));
+}
+
+#[test]
+#[should_panic]
+fn xor_into_rejects_mismatched_lengths() {
+    let mut v = b"hello".to_vec();
+    xor_into(&mut v, b"he");
 }
 
 #[test]
@@ -73,37 +229,305 @@ fn transpose_matrix() {
 
 #[test]
 fn base64_decode() {
-    let Base64(x1) = string_to_base64("TWFu");
-    let result = raw_to_string(&x1);
-    assert!(result == "Man");
+    let x1: Base64 = "TWFu".parse().unwrap();
+    assert!(&x1[..] == b"Man");
+
+    let x2: Base64 = "YW55IGNhcm5hbCBwbGVhc3VyZS4=".parse().unwrap();
+    assert!(&x2[..] == b"any carnal pleasure.");
+
+    let x3: Base64 = "YW55IGNhcm5hbCBwbGVhcw==".parse().unwrap();
+    assert!(&x3[..] == b"any carnal pleas");
+    assert!(x3 == "YW55IGNhcm5hbCBwbGVhcw==");
+    assert!(format!("{}", x3) == "YW55IGNhcm5hbCBwbGVhcw==");
+}
+
+#[test]
+fn base64_encode() {
+    assert!(encode_base64(b"") == "");
+    assert!(encode_base64(b"M") == "TQ==");
+    assert!(encode_base64(b"Ma") == "TWE=");
+    assert!(encode_base64(b"Man") == "TWFu");
+    assert!(encode_base64(&[0, 0, 0]) == "AAAA");
+    assert!(encode_base64(b"any carnal pleas") == "YW55IGNhcm5hbCBwbGVhcw==");
+}
+
+#[test]
+fn base64_variants() {
+    let data = vec![0xfb, 0xff, 0xbf, 0x61];
+    assert!(BASE64_STANDARD.encode(&data) == "+/+/YQ==");
+    assert!(BASE64_URL_SAFE.encode(&data) == "-_-_YQ");
+    let Base64(decoded) = BASE64_URL_SAFE.decode("-_-_YQ").unwrap();
+    assert!(decoded == data);
+    assert!(BASE64_URL_SAFE.decode("-_-_YQ==").err() == Some(DecodeError::InvalidPadding('=', 6)));
+    assert!(BASE64_STANDARD.decode("+/+/YQ").err() == Some(DecodeError::TruncatedGroup(4)));
+
+    let long: Vec<u8> = (0..100).collect();
+    let wrapped = BASE64_MIME.encode(&long);
+    let lines: Vec<&str> = wrapped.split("\r\n").collect();
+    assert!(lines.len() == 2 && lines[0].len() == 76);
+    let Base64(decoded) = BASE64_MIME.decode(&wrapped).unwrap();
+    assert!(decoded == long);
+    let Base64(decoded) = BASE64_MIME.decode(" TW\tFu\r\nTWFu\n").unwrap();
+    assert!(decoded == b"ManMan".to_vec());
+    assert!(BASE64_STANDARD.decode("TWFu\nTWF").err() == Some(DecodeError::InvalidCharacter('\n', 4)));
+}
+
+#[test]
+fn bytes_conversion() {
+    let raw = Bytes(vec![0x00, b'a', b'\\', b'\n', 0x7f, 0xe9, 0xff]);
+    assert!(raw.to_escaped() == "\\x00a\\\\\\n\\x7f\\xe9\\xff");
+    assert!(format!("{}", raw) == raw.to_escaped());
+    assert!(Bytes::from_escaped(&raw.to_escaped()) == Ok(raw.clone()));
+    assert!(Bytes::from_escaped("\\x4").err() == Some(DecodeError::TruncatedGroup(0)));
+    assert!(Bytes::from_escaped("\\q").err() == Some(DecodeError::InvalidCharacter('q', 1)));
+
+    assert!(Bytes::from_latin1(&raw.to_latin1()) == Ok(raw.clone()));
+    assert!(Bytes::from_latin1("caf\u{e9}") == Ok(Bytes(vec![b'c', b'a', b'f', 0xe9])));
+    assert!(Bytes::from_latin1("\u{263a}").err() == Some(DecodeError::InvalidCharacter('\u{263a}', 0)));
+
+    let utf8 = Bytes::from_utf8("caf\u{e9}");
+    assert!(utf8 == Bytes(vec![b'c', b'a', b'f', 0xc3, 0xa9]));
+    assert!(utf8.to_utf8().unwrap() == "caf\u{e9}");
+    assert!(raw.to_utf8().is_err());
+}
+
+#[test]
+fn bytes_escaping_idempotent() {
+    fn equality_after_round_trip(v: Vec<u8>) -> bool {
+        let raw = Bytes(v);
+        Bytes::from_escaped(&raw.to_escaped()) == Ok(raw.clone()) &&
+            Bytes::from_latin1(&raw.to_latin1()) == Ok(raw)
+    }
+    quickcheck(equality_after_round_trip as fn(Vec<u8>) -> bool);
+}
+
+#[test]
+fn hex_type_conversions() {
+    let hex: Hex = "00ff10".parse().unwrap();
+    assert!(&hex[..] == &[0x00, 0xff, 0x10]);
+    assert!(hex.len() == 3);
+    assert!(hex == "00FF10" && hex == "00ff10" && hex != "00ff11");
+    assert!(hex.to_string() == "00ff10");
+    assert!(hex_to_string(hex.clone()) == "00ff10");
+    assert!(format!("{:x}", hex) == "00ff10");
+    assert!(format!("{:X}", hex) == "00FF10");
+    assert!(Base64::from(hex.clone()) == "AP8Q");
+    assert!(Hex::from(vec![0x00, 0xff, 0x10]) == hex);
+    let v: Vec<u8> = hex.into();
+    assert!(v == vec![0x00, 0xff, 0x10]);
+    assert!("0x".parse::<Hex>().is_err());
+}
+
+#[test]
+fn hex_encoding_options() {
+    let hex = Hex(vec![0xde, 0xad, 0xbe, 0xef, 0x01]);
+    assert!(hex.encode_with(&HEX_UPPER) == "DEADBEEF01");
+    assert!(hex.encode_with(&HEX_LOWER) == "deadbeef01");
+    assert!(hex.encode_with(&HEX_COLON) == "de:ad:be:ef:01");
+    assert!(hex.encode_with(&HEX_SPACED) == "de ad be ef 01");
+    let grouped = HexConfig { case: Case::Upper, separator: " ", group_size: 2 };
+    assert!(hex.encode_with(&grouped) == "DEAD BEEF 01");
+    assert!(Hex::decode_with("DE:AD:be:ef:01", &HEX_COLON) == Ok(hex.clone()));
+    assert!(Hex::decode_with("DEAD BEEF 01", &grouped) == Ok(hex.clone()));
+    assert!(Hex::decode_with("de:a:be", &HEX_COLON).err() == Some(DecodeError::TruncatedGroup(3)));
+    assert!(Hex::decode_with("de:ag", &HEX_COLON).err() == Some(DecodeError::InvalidCharacter('g', 4)));
+}
+
+#[test]
+fn hexdump_layout() {
+    let dump = hexdump(b"I'm killing your brain\x00\xff");
+    assert!(dump == "00000000: 4927 6d20 6b69 6c6c 696e 6720 796f 7572  I'm killing your\n\
+                     00000010: 2062 7261 696e 00ff                       brain..\n");
+    assert!(hexdump(b"") == "");
+}
+
+#[test]
+fn base32_vectors() {
+    let vectors = [("", ""), ("f", "MY======"), ("fo", "MZXQ===="), ("foo", "MZXW6==="),
+                   ("foob", "MZXW6YQ="), ("fooba", "MZXW6YTB"), ("foobar", "MZXW6YTBOI======")];
+    for &(plain, encoded) in vectors.iter() {
+        assert!(encode_base32(plain.as_bytes()) == encoded);
+        assert!(&decode_base32(encoded).unwrap()[..] == plain.as_bytes());
+    }
+    assert!(&decode_base32("mzxw6ytboi").unwrap()[..] == b"foobar");
+    assert!(decode_base32("MZXW6YTBO").err() == Some(DecodeError::TruncatedGroup(8)));
+    assert!(decode_base32("MY=====").err() == Some(DecodeError::InvalidPadding('=', 2)));
+    assert!(decode_base32("M1======").err() == Some(DecodeError::InvalidCharacter('1', 1)));
+}
+
+#[test]
+fn ascii85_vectors() {
+    assert!(encode_ascii85(b"Man is distinguished") == "9jqo^BlbD-BleB1DJ+*+F(f,q");
+    assert!(encode_ascii85(b"sure.") == "F*2M7/c");
+    assert!(encode_ascii85(&[0, 0, 0, 0, 0]) == "z!!");
+    assert!(&decode_ascii85("<~9jqo^BlbD-BleB1DJ+*+F(f,q~>").unwrap()[..] == b"Man is distinguished");
+    assert!(&decode_ascii85("F*2M7\n/c").unwrap()[..] == b"sure.");
+    assert!(&decode_ascii85("z!!").unwrap()[..] == &[0, 0, 0, 0, 0]);
+    assert!(decode_ascii85("F*2M7/").err() == Some(DecodeError::TruncatedGroup(5)));
+    assert!(decode_ascii85("F*zM7").err() == Some(DecodeError::InvalidCharacter('z', 2)));
+    assert!(decode_ascii85("uuuuu").err() == Some(DecodeError::InvalidCharacter('u', 4)));
+}
+
+#[test]
+fn percent_encoding() {
+    assert!(encode_percent(b";admin=true; a~b") == "%3Badmin%3Dtrue%3B%20a~b");
+    assert!(&decode_percent("%3badmin%3Dtrue").unwrap()[..] == b";admin=true");
+    assert!(&decode_percent("caf\u{e9}").unwrap()[..] == "caf\u{e9}".as_bytes());
+    assert!(decode_percent("%3").err() == Some(DecodeError::TruncatedGroup(0)));
+    assert!(decode_percent("%3g").err() == Some(DecodeError::InvalidCharacter('g', 2)));
+    assert!(Percent(b"a b".to_vec()).to_string() == "a%20b");
+}
+
+#[test]
+fn extra_encodings_idempotent() {
+    fn equality_after_round_trip(v: Vec<u8>) -> bool {
+        let base32: Base32 = Base32(v.clone()).to_string().parse().unwrap();
+        let ascii85: Ascii85 = Ascii85(v.clone()).to_string().parse().unwrap();
+        let percent: Percent = Percent(v.clone()).to_string().parse().unwrap();
+        base32[..] == v[..] && ascii85[..] == v[..] && percent[..] == v[..]
+    }
+    quickcheck(equality_after_round_trip as fn(Vec<u8>) -> bool);
+}
 
-    let Base64(x2) = string_to_base64("YW55IGNhcm5hbCBwbGVhc3VyZS4=");
-    let result = raw_to_string(&x2);
-    assert!(result == "any carnal pleasure.");
+#[test]
+fn hex_decode_errors() {
+    assert!(decode_hex("0g").err() == Some(DecodeError::InvalidCharacter('g', 1)));
+    assert!(decode_hex("abc").is_ok());
+}
 
-    let Base64(x3) = string_to_base64("YW55IGNhcm5hbCBwbGVhcw==");
-    let result = raw_to_string(&x3);
-    assert!(result == "any carnal pleas");
+#[test]
+fn base64_decode_errors() {
+    assert!(decode_base64("TWF").err() == Some(DecodeError::TruncatedGroup(0)));
+    assert!(decode_base64("TW!u").err() == Some(DecodeError::InvalidCharacter('!', 2)));
+    assert!(decode_base64("TW=u").err() == Some(DecodeError::InvalidPadding('=', 2)));
+    assert!(decode_base64("TQ==TWFu").err() == Some(DecodeError::InvalidPadding('=', 2)));
+    assert!(decode_base64("T===").err() == Some(DecodeError::InvalidPadding('=', 1)));
 }
 
 #[test]
 fn challenge_6() {
     let Base64(block) = read_base64_file("data/6.txt");
     let keysizes = likely_keysizes(&block, 2..41);
-    let keys = break_repeating_key_xor(&block, &keysizes[..3].to_vec());
+    let keys = break_repeating_key_xor(&block, &keysizes[..3].to_vec(), &NgramModel::english(2));
     let ref key = keys[0];
     assert!(key == "Terminator X: Bring the noise");
 
-    let result = xor_key(&block, &key);
+    let result = xor_key(&block, &key).unwrap();
     let decrypted = raw_to_string(&result);
     let snippet = "I\'m back and I\'m ringin\' the bell \nA rockin\' on the mike while the fly girls yell \nIn ecstasy in the back of me \nWell that\'s my DJ Deshay cuttin\' all them Z\'s \nHittin\' hard and the girlies goin\' crazy \nVanilla\'s on the mike, man I\'m not lazy.";
     assert!(&decrypted[..snippet.len()] == snippet);
 }
 
+#[test]
+fn challenge_6_solver() {
+    let Base64(block) = read_base64_file("data/6.txt");
+    let solutions = solve_repeating_key_xor(&block, 2..41, 3, &NgramModel::english(2));
+    assert!(solutions[0].key == b"Terminator X: Bring the noise".to_vec());
+    assert!(solutions[0].plaintext.starts_with(b"I'm back and I'm ringin' the bell"));
+    assert!(solutions.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[test]
+fn repeating_key_solver() {
+    let Base64(block) = read_base64_file("data/6.txt");
+    let plaintext = xor_key(&block, "Terminator X: Bring the noise").unwrap();
+
+    // a binary key, and the solver shouldn't report its repetitions.
+    let key = vec![0x8f, 0x00, 0xd3, 0x41, 0xfe, 0x7a, 0x10];
+    let encrypted = xor_key_bytes(&plaintext[..600], &key);
+    let solutions = solve_repeating_key_xor(&encrypted, 2..30, 4, &NgramModel::english(2));
+    assert!(solutions[0].key == key);
+    assert!(solutions[0].plaintext == plaintext[..600].to_vec());
+    assert!(solutions.iter().all(|s| shortest_period(&s.key) == &s.key[..]));
+
+    assert!(shortest_period(b"ICEICE") == b"ICE");
+    assert!(shortest_period(b"ICEIC") == b"ICEIC");
+    assert!(shortest_period(b"") == b"");
+}
+
+/// the non-empty lines of challenge 6's plaintext, for the keystream reuse
+/// tests.
+pub fn vanilla_lines() -> Vec<Vec<u8>> {
+    let Base64(block) = read_base64_file("data/6.txt");
+    let plaintext = xor_key(&block, "Terminator X: Bring the noise").unwrap();
+    plaintext.split(|&c| c == b'\n')
+        .filter(|l| l.len() > 0)
+        .map(|l| l.to_vec())
+        .collect()
+}
+
+#[test]
+fn many_time_pad() {
+    let lines = vanilla_lines();
+    let length = lines.iter().map(|l| l.len()).max().unwrap();
+    let mut rng = thread_rng();
+    let keystream: Vec<u8> = (0..).take(length).map(|_| rng.gen::<u8>()).collect();
+    let ciphertexts: Vec<Vec<u8>> = lines.iter().map(|l| xor(l, &keystream)).collect();
+
+    let mut pad = ManyTimePad::new(ciphertexts);
+    pad.solve(&ChiSquared::english());
+
+    // most of the columns that plenty of lines reach are right.
+    let columns = transpose(&lines);
+    let deep: Vec<usize> = (0..length).filter(|&i| columns[i].len() >= 20).collect();
+    let correct = deep.iter().filter(|&&i| pad.keystream()[i] == keystream[i]).count();
+    assert!(correct * 10 >= deep.len() * 9);
+
+    // knowing one line gives every other line over the same span.
+    let known = lines[0].clone();
+    pad.pin(0, 0, &known).unwrap();
+    assert!(pad.plaintext(0) == Some(known.clone()));
+    for (i, line) in lines.iter().enumerate() {
+        let n = ::std::cmp::min(line.len(), known.len());
+        assert!(pad.plaintext(i).unwrap()[..n] == line[..n]);
+    }
+    assert!(pad.is_pinned(0) && !pad.is_pinned(known.len()));
+
+    // pinned bytes survive another pass.
+    pad.solve(&NgramModel::english(1));
+    assert!(pad.plaintext(0) == Some(known.clone()));
+    pad.unpin(0, known.len());
+    assert!(!pad.is_pinned(0));
+    pad.pin(0, 0, &known).unwrap();
+    pad.unpin(1, usize::max_value());
+    assert!(pad.is_pinned(0) && !pad.is_pinned(1));
+    pad.unpin(usize::max_value(), usize::max_value());
+
+    assert!(pad.pin(lines.len(), 0, b"x") == Err(CribError::NoSuchMessage(lines.len())));
+    assert!(pad.pin(0, 1, &known) == Err(CribError::TooLong(known.len())));
+
+    // nothing to solve is not an error.
+    let mut empty = ManyTimePad::new(vec![]);
+    empty.solve(&ChiSquared::english());
+    assert!(empty.keystream().is_empty() && empty.plaintexts().is_empty());
+}
+
+#[test]
+fn crib_dragging() {
+    let first = b"Now that the party is jumping".to_vec();
+    let second = b"Cooking MC's like a pound of bacon".to_vec();
+    let keystream: Vec<u8> = random_aes().iter().cycle().take(second.len()).cloned().collect();
+    let a = xor(&first, &keystream);
+    let b = xor(&second, &keystream);
+
+    let result = crib_drag(&a, &b, b"the party", &NgramModel::english(2));
+    assert!(result.len() == first.len() - 8);
+    assert!(result.windows(2).all(|w| w[0].score >= w[1].score));
+    assert!(result[0].offset == 9);
+    assert!(result[0].fragment == b"C's like ".to_vec());
+
+    // symmetric, apart from which plaintext is revealed.
+    let reverse = crib_drag(&b, &a, b"C's like ", &NgramModel::english(2));
+    assert!(reverse.iter().any(|m| m.offset == 9 && m.fragment == b"the party".to_vec()));
+
+    assert!(crib_drag(&a, &b, b"", &PrintableRatio).is_empty());
+    assert!(crib_drag(&a, &b, &second, &PrintableRatio).is_empty());
+}
+
 #[test]
 fn challenge_7() {
     let Base64(block) = read_base64_file("data/7.txt");
-    let result = raw_to_string(&decrypt_aes_ecb(&block, "YELLOW SUBMARINE"));
+    let result = raw_to_string(&decrypt_aes_ecb(&block, b"YELLOW SUBMARINE").unwrap());
     let n = result.len();
     // result is pkcs7 padded
     assert!(&result[n-27..n-4] == "Play that funky music \n");
@@ -121,6 +545,138 @@ fn challenge_8() {
     }
 }
 
+#[test]
+fn challenge_7_streaming() {
+    let f = File::open("data/7.txt").unwrap();
+    let mut reader = Base64Reader::with_config(f, &BASE64_PEM);
+    let mut block = vec![];
+    reader.read_to_end(&mut block).unwrap();
+    let result = raw_to_string(&decrypt_aes_ecb(&block, b"YELLOW SUBMARINE").unwrap());
+    assert!(result.starts_with("I'm back and I'm ringin' the bell"));
+}
+
+#[test]
+fn streaming_decode_errors() {
+    let mut v = vec![];
+    let mut reader = HexReader::new(Cursor::new("00 ff\n1"));
+    let e = reader.read_to_end(&mut v).unwrap_err();
+    assert!(e.get_ref().unwrap().downcast_ref::<DecodeError>() == Some(&DecodeError::TruncatedGroup(6)));
+
+    let mut reader = Base64Reader::new(Cursor::new("TWFu\nTWFu"));
+    let e = reader.read_to_end(&mut v).unwrap_err();
+    assert!(e.get_ref().unwrap().downcast_ref::<DecodeError>() == Some(&DecodeError::InvalidCharacter('\n', 4)));
+
+    let mut reader = Base64Reader::new(Cursor::new("TQ==TWFu"));
+    let e = reader.read_to_end(&mut v).unwrap_err();
+    assert!(e.get_ref().unwrap().downcast_ref::<DecodeError>() == Some(&DecodeError::InvalidPadding('=', 2)));
+
+    // what came before the error is kept, and the error doesn't turn into EOF.
+    let mut buffer = [0; 16];
+    let mut reader = HexReader::new(Cursor::new("00ff1x22"));
+    assert!(reader.read(&mut buffer).unwrap() == 2 && buffer[..2] == [0, 0xff]);
+    for _ in 0..2 {
+        let e = reader.read(&mut buffer).unwrap_err();
+        assert!(e.get_ref().unwrap().downcast_ref::<DecodeError>() == Some(&DecodeError::InvalidCharacter('x', 5)));
+    }
+    let mut reader = Base64Reader::new(Cursor::new("TWFuTW!u"));
+    assert!(reader.read(&mut buffer).unwrap() == 3 && buffer[..3] == b"Man"[..]);
+    for _ in 0..2 {
+        let e = reader.read(&mut buffer).unwrap_err();
+        assert!(e.get_ref().unwrap().downcast_ref::<DecodeError>() == Some(&DecodeError::InvalidCharacter('!', 6)));
+    }
+    let mut reader = HexReader::new(Cursor::new("0"));
+    assert!(reader.read(&mut buffer).is_err() && reader.read(&mut buffer).is_err());
+
+    match try_read_base64_file("data/4.txt") {
+        Err(ReadError::Decode(_)) => (),
+        other => panic!("expected a decode error, got {:?}", other)
+    }
+    match try_read_base64_file("data/missing.txt") {
+        Err(ReadError::Io(_)) => (),
+        other => panic!("expected an io error, got {:?}", other)
+    }
+}
+
+#[test]
+fn streaming_round_trip() {
+    fn equality_after_round_trip(v: Vec<u8>, split: usize) -> bool {
+        let split = if v.len() > 0 { split % v.len() } else { 0 };
+
+        let mut writer = Base64Writer::with_config(vec![], &BASE64_MIME);
+        writer.write_all(&v[..split]).unwrap();
+        writer.write_all(&v[split..]).unwrap();
+        let encoded = writer.finish().unwrap();
+        let mut decoded = vec![];
+        Base64Reader::with_config(Cursor::new(&encoded), &BASE64_MIME).read_to_end(&mut decoded).unwrap();
+        let batch = BASE64_MIME.encode(&v);
+
+        let mut writer = HexWriter::new(vec![]);
+        writer.write_all(&v).unwrap();
+        let hex = writer.into_inner();
+        let mut unhexed = vec![];
+        HexReader::new(Cursor::new(&hex)).read_to_end(&mut unhexed).unwrap();
+
+        decoded == v && encoded == batch.as_bytes() && unhexed == v
+    }
+    quickcheck(equality_after_round_trip as fn(Vec<u8>, usize) -> bool);
+}
+
+#[test]
+fn detect_file_formats() {
+    let (format, records) = load_file("data/4.txt");
+    assert!(format == FileFormat { encoding: Encoding::Hex, layout: Layout::Lines });
+    assert!(records == read_hexlines_file("data/4.txt"));
+
+    let (format, records) = load_file("data/8.txt");
+    assert!(format == FileFormat { encoding: Encoding::Hex, layout: Layout::Lines });
+    assert!(records.len() == 204 && records.iter().all(|r| r.len() == 160));
+    assert!(read_hexlines_file("data/8.txt").len() == 204);
+
+    let (format, records) = load_file("data/6.txt");
+    assert!(format == FileFormat { encoding: Encoding::Base64, layout: Layout::Whole });
+    let Base64(block) = read_base64_file("data/6.txt");
+    assert!(records == vec![block]);
+
+    let tokens = b"eyJhbGciOiJub25lIn0\nPz8_Pw\n";
+    let format = detect_format(tokens);
+    assert!(format == FileFormat { encoding: Encoding::Base64UrlSafe, layout: Layout::Lines });
+    let records = decode_records(tokens, &format).unwrap();
+    assert!(records == vec![b"{\"alg\":\"none\"}".to_vec(), b"????".to_vec()]);
+
+    let lines = b"TWFu\r\nTQ==\r\nTWE=\r\n";
+    let format = detect_format(lines);
+    assert!(format == FileFormat { encoding: Encoding::Base64, layout: Layout::Lines });
+    assert!(decode_records(lines, &format).unwrap() == vec![b"Man".to_vec(), b"M".to_vec(), b"Ma".to_vec()]);
+
+    // stray whitespace doesn't hide the encoding.
+    let spaced = b"TWFu \n";
+    let format = detect_format(spaced);
+    assert!(format == FileFormat { encoding: Encoding::Base64, layout: Layout::Whole });
+    assert!(decode_records(spaced, &format).unwrap() == vec![b"Man".to_vec()]);
+    let spaced = b" TQ==\nTWFu \n";
+    let format = detect_format(spaced);
+    assert!(format == FileFormat { encoding: Encoding::Base64, layout: Layout::Lines });
+    assert!(decode_records(spaced, &format).unwrap() == vec![b"M".to_vec(), b"Man".to_vec()]);
+
+    // odd length hex gets a leading zero, as with `decode_hex`.
+    let odd = b"abc\ndef0\n";
+    let format = detect_format(odd);
+    assert!(format == FileFormat { encoding: Encoding::Hex, layout: Layout::Lines });
+    assert!(decode_records(odd, &format).unwrap() == vec![vec![0x0a, 0xbc], vec![0xde, 0xf0]]);
+    let format = detect_format(b" 0ff \n");
+    assert!(format == FileFormat { encoding: Encoding::Hex, layout: Layout::Whole });
+    assert!(decode_records(b" 0ff \n", &format).unwrap() == vec![vec![0x00, 0xff]]);
+
+    let raw = [0x00, 0xff, 0x10, 0x0a];
+    let format = detect_format(&raw);
+    assert!(format == FileFormat { encoding: Encoding::Binary, layout: Layout::Whole });
+    assert!(decode_records(&raw, &format).unwrap() == vec![raw.to_vec()]);
+
+    let bad = b"00ff\n0g\n";
+    let format = FileFormat { encoding: Encoding::Hex, layout: Layout::Lines };
+    assert!(decode_records(bad, &format).err() == Some(DecodeError::InvalidCharacter('g', 6)));
+}
+
 #[derive(Clone, Debug)]
 struct Text {
     value: String
@@ -133,8 +689,8 @@ impl Arbitrary for Text {
         len += len % 2; // must be even
         for _ in 0..len {
             let i = usize::arbitrary(g);
-            let i = i % HEX_ALPHABET.len();
-            s.push(HEX_ALPHABET.as_bytes()[i] as char);
+            let i = i % HEX_LOWER_ALPHABET.len();
+            s.push(HEX_LOWER_ALPHABET.as_bytes()[i] as char);
         }
         Text { value: s }
     }
@@ -143,7 +699,27 @@ impl Arbitrary for Text {
 #[test]
 fn hex_conversion_idempotent() {
     fn equality_after_applying_twice(t: Text) -> bool {
-        t.value == hex_to_string(string_to_hex(&t.value[..]))
+        let hex: Hex = t.value.parse().unwrap();
+        t.value == hex.to_string()
     }
     quickcheck(equality_after_applying_twice as fn(Text) -> bool);
 }
+
+#[test]
+fn base64_conversion_idempotent() {
+    fn equality_after_round_trip(v: Vec<u8>) -> bool {
+        let encoded = Base64::from(v.clone()).to_string();
+        let decoded: Base64 = encoded.parse().unwrap();
+        decoded == Base64(v)
+    }
+    quickcheck(equality_after_round_trip as fn(Vec<u8>) -> bool);
+}
+
+#[test]
+fn base64_encoding_is_padded() {
+    fn length_is_multiple_of_four(v: Vec<u8>) -> bool {
+        let encoded = encode_base64(&v);
+        encoded.len() % 4 == 0 && encoded.len() == (v.len() + 2) / 3 * 4
+    }
+    quickcheck(length_is_multiple_of_four as fn(Vec<u8>) -> bool);
+}
@@ -1,54 +1,177 @@
 
 use std::{iter};
+use quickcheck::quickcheck;
 use rand::distributions::{IndependentSample, Range};
 use rand::{thread_rng, Rng};
 
+use aes::*;
+use blocks::*;
+use cipher::*;
 use conversion::*;
 use crypto::*;
+use form::*;
+use padding::*;
 use util::*;
 
 #[test]
 fn challenge_9() {
     let sample = "YELLOW SUBMARINE";
-    let result = pad_pkcs7(&string_to_raw(sample), 20);
+    let result = Pkcs7.pad(&string_to_raw(sample).unwrap(), 20);
     assert!(raw_to_string(&result) == "YELLOW SUBMARINE\u{04}\u{04}\u{04}\u{04}")
 }
 
 #[test]
 fn decrypt_encrypt_ecb() {
-    let key = "YELLOW SUBMARINE";
+    let key = b"YELLOW SUBMARINE";
     let sample = "a test a testing";
-    let encrypted = encrypt_aes_ecb(&string_to_raw(sample), key);
-    let decrypted = raw_to_string(&decrypt_aes_ecb(&encrypted, key));
+    let encrypted = encrypt_aes_ecb(&string_to_raw(sample).unwrap(), key).unwrap();
+    let decrypted = raw_to_string(&decrypt_aes_ecb(&encrypted, key).unwrap());
     assert!(decrypted == sample);
 }
 
 #[test]
 fn decrypt_encrypt_cbc() {
-    let key = "YELLOW SUBMARINE";
+    let key = b"YELLOW SUBMARINE";
     let sample = "a test a testing";
     let iv: Vec<u8> = iter::repeat(0).take(16).collect();
-    let encrypted = encrypt_aes_cbc(&iv, &string_to_raw(sample), key);
-    let decrypted = raw_to_string(&decrypt_aes_cbc(&iv, &encrypted, key));
+    let encrypted = encrypt_aes_cbc(&iv, &string_to_raw(sample).unwrap(), key).unwrap();
+    let decrypted = raw_to_string(&decrypt_aes_cbc(&iv, &encrypted, key).unwrap());
     assert!(decrypted == sample);
 }
 
 #[test]
 fn decrypt_encrypt_cbc_complex() {
-    let key = "YELLOW SUBMARINE";
+    let key = b"YELLOW SUBMARINE";
     let sample = "a test a testing - and now for something significantly longer...";
     let iv = random_aes();
-    let encrypted = encrypt_aes_cbc(&iv, &string_to_raw(sample), key);
-    let decrypted = raw_to_string(&decrypt_aes_cbc(&iv, &encrypted, key));
+    let encrypted = encrypt_aes_cbc(&iv, &string_to_raw(sample).unwrap(), key).unwrap();
+    let decrypted = raw_to_string(&decrypt_aes_cbc(&iv, &encrypted, key).unwrap());
     assert!(decrypted == sample);
 }
 
+#[test]
+fn aes_key_sizes() {
+    let Hex(plaintext) = "00112233445566778899aabbccddeeff".parse().unwrap();
+    let Hex(key) = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f".parse().unwrap();
+    let encrypted = encrypt_aes_ecb(&plaintext, &key).unwrap();
+    assert!(Hex(encrypted.clone()) == "8ea2b7ca516745bfeafc49904b496089");
+    assert!(decrypt_aes_ecb(&encrypted, &key).unwrap() == plaintext);
+
+    // always 16 byte blocks, whatever the key length.
+    let sample = string_to_raw("a test a testing - and now for something significantly longer...").unwrap();
+    let iv = random_aes();
+    for &n in [16, 24, 32].iter() {
+        let key = &key[..n];
+        let encrypted = encrypt_aes_cbc(&iv, &sample, key).unwrap();
+        assert!(encrypted.len() == 64);
+        assert!(encrypted == cbc_encrypt(&Aes::new(key), &iv, &sample));
+        assert!(decrypt_aes_cbc(&iv, &encrypted, key).unwrap() == sample[..64].to_vec());
+    }
+
+    assert!(encrypt_aes_ecb(&plaintext, &key[..20]) == Err(CipherError::InvalidKeyLength(20)));
+    assert!(decrypt_aes_ecb(&plaintext, b"") == Err(CipherError::InvalidKeyLength(0)));
+    assert!(encrypt_aes_cbc(&iv, &plaintext, &key[..31]) ==
+            Err(CipherError::InvalidKeyLength(31)));
+    assert!(decrypt_aes_cbc(&key, &plaintext, &key[..16]) == Err(CipherError::InvalidIvLength(32)));
+}
+
+#[test]
+fn aes_fips_197() {
+    // appendix C, one key length at a time.
+    let Hex(plaintext) = "00112233445566778899aabbccddeeff".parse().unwrap();
+    let vectors = [("000102030405060708090a0b0c0d0e0f",
+                    "69c4e0d86a7b0430d8cdb78070b4c55a"),
+                   ("000102030405060708090a0b0c0d0e0f1011121314151617",
+                    "dda97ca4864cdfe06eaf70a0ec0d7191"),
+                   ("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+                    "8ea2b7ca516745bfeafc49904b496089")];
+    for &(key, expected) in vectors.iter() {
+        let Hex(key) = key.parse().unwrap();
+        let aes = Aes::new(&key);
+        assert!(aes.rounds() == key.len() / 4 + 6);
+        let mut block = plaintext.clone();
+        aes.encrypt_block(&mut block);
+        assert!(Hex(block.clone()) == expected);
+        aes.decrypt_block(&mut block);
+        assert!(block == plaintext);
+    }
+
+    // appendix A.1's key expansion and appendix B's worked example.
+    let Hex(key) = "2b7e151628aed2a6abf7158809cf4f3c".parse().unwrap();
+    let aes = Aes::new(&key);
+    assert!(Hex(aes.round_keys()[10].to_vec()) == "d014f9a8c9ee2589e13f0cc8b6630ca6");
+    let Hex(mut block) = "3243f6a8885a308d313198a2e0370734".parse().unwrap();
+    aes.encrypt_block(&mut block);
+    assert!(Hex(block) == "3925841d02dc09fbdc118597196a0b32");
+
+    // and it agrees with whichever backend provides Aes128 and Aes256.
+    let key = random_aes();
+    let data: Vec<u8> = (0..64).collect();
+    assert!(ecb_encrypt(&Aes::new(&key), &data) == encrypt_aes_ecb(&data, &key).unwrap());
+    assert!(ecb_encrypt(&Aes::new(&key), &data) == ecb_encrypt(&Aes128::new(&key), &data));
+    let mut key = key;
+    key.extend(random_aes());
+    assert!(ecb_encrypt(&Aes::new(&key), &data) == ecb_encrypt(&Aes256::new(&key), &data));
+}
+
+// not a cipher at all, but enough to check the modes only use the trait.
+struct Reverse(u8);
+
+impl BlockCipher for Reverse {
+    fn block_size(&self) -> usize {
+        5
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        block.reverse();
+        for x in block.iter_mut() {
+            *x = x.wrapping_add(self.0);
+        }
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        for x in block.iter_mut() {
+            *x = x.wrapping_sub(self.0);
+        }
+        block.reverse();
+    }
+}
+
+#[test]
+fn generic_block_modes() {
+    let sample = string_to_raw("a test a testing, and then some").unwrap();
+    let ciphers: Vec<Box<BlockCipher>> = vec![Box::new(Reverse(3)),
+                                              Box::new(Aes128::new(b"YELLOW SUBMARINE"))];
+    for cipher in ciphers.iter() {
+        let n = cipher.block_size();
+        let whole = &sample[..sample.len() - sample.len() % n];
+        let iv: Vec<u8> = (0..n as u8).collect();
+        let encrypted = ecb_encrypt(&**cipher, &sample);
+        assert!(encrypted.len() == whole.len() && encrypted != whole);
+        assert!(ecb_decrypt(&**cipher, &encrypted) == whole);
+        let encrypted = cbc_encrypt(&**cipher, &iv, &sample);
+        assert!(encrypted.len() == whole.len());
+        assert!(cbc_decrypt(&**cipher, &iv, &encrypted) == whole);
+    }
+
+    // the key schedule is reused across calls.
+    let cipher = Aes128::new(b"YELLOW SUBMARINE");
+    let mut block = string_to_raw("a test a testing").unwrap();
+    cipher.encrypt_block(&mut block);
+    let expected = encrypt_aes_ecb(&string_to_raw("a test a testing").unwrap(), b"YELLOW SUBMARINE").unwrap();
+    assert!(block == expected);
+    cipher.decrypt_block(&mut block);
+    cipher.decrypt_block(&mut block);
+    cipher.encrypt_block(&mut block);
+    assert!(block == string_to_raw("a test a testing").unwrap());
+}
+
 #[test]
 fn challenge_10() {
     let Base64(block) = read_base64_file("data/10.txt");
-    let key = "YELLOW SUBMARINE";
+    let key = b"YELLOW SUBMARINE";
     let iv: Vec<u8> = iter::repeat(0).take(16).collect();
-    let result = decrypt_aes_cbc(&iv, &block, key);
+    let result = decrypt_aes_cbc(&iv, &block, key).unwrap();
     let decrypted = raw_to_string(&result);
     let snippet = "I\'m back and I\'m ringin\' the bell ";
     assert!(&decrypted[..snippet.len()] == snippet);
@@ -69,117 +192,257 @@ fn challenge_11() {
 #[test]
 fn challenge_12() {
     let mystery_string = "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkgaGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBqdXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUgYnkK";
-    let Base64(mystery) = string_to_base64(&mystery_string);
-    let key = random_string(16);
-    let oracle = create_simple_oracle(&mystery, &key);
+    let Base64(mystery) = mystery_string.parse().unwrap();
+    let key = random_aes();
+    let oracle = create_simple_oracle(&mystery, Aes128::new(&key));
     let result = decrypt_ecb(oracle);
     // we may be decrypting past the known string due to the
     // ciphertext being rounded up to the nearest block size.
-    assert!(result[..mystery.len()] == raw_to_string(&mystery));
+    assert!(result[..mystery.len()] == mystery[..]);
 }
 
 #[test]
 fn test_key_value() {
-    let query = "foo=bar&baz=qux&zap=zazzle";
+    let form = Form::parse_urlencoded("foo=bar&baz=qux&zap=zazzle").unwrap();
     let desired = vec![("foo", "bar"), ("baz", "qux"), ("zap", "zazzle")];
     let result: Vec<(String, String)> = desired.iter().map(|&(k, v)| {
         (k.to_string(),
          v.to_string())
     }).collect();
-    assert!(key_value(query) == result);
+    assert!(form.pairs() == &result[..]);
+    assert!(form.to_urlencoded() == "foo=bar&baz=qux&zap=zazzle");
+}
+
+#[test]
+fn form_encoding() {
+    let form = Form::parse_urlencoded("a=1&flag&b=two+words&a=%32&&c=100%&d=%zz%41").unwrap();
+    assert!(form.get("a") == Some("1"));
+    assert!(form.get_all("a") == vec!["1", "2"]);
+    assert!(form.get("flag") == Some(""));
+    assert!(form.get("b") == Some("two words"));
+    assert!(form.get("c") == Some("100%"));
+    assert!(form.get("d") == Some("%zzA"));
+    assert!(form.get("e") == None);
+    assert!(form.pairs().len() == 6);
+
+    let mut form = Form::new();
+    form.push("k&=;", "a b+c\u{ff}\u{263a}");
+    assert!(form.to_urlencoded() == "k%26%3D%3B=a+b%2Bc%C3%BF%E2%98%BA");
+    assert!(form.to_cookie() == "k%26%3D%3B=a%20b%2Bc%C3%BF%E2%98%BA");
+    assert!(Form::parse_urlencoded(&form.to_urlencoded()) == Ok(form.clone()));
+    assert!(Form::parse_cookie(&form.to_cookie()) == Ok(form));
+
+    // escapes are utf-8, and the error points at the one that isn't.
+    assert!(Form::parse_urlencoded("k=\u{ff}%C3%BF") == Ok(Form::parse_urlencoded("k=\u{ff}\u{ff}").unwrap()));
+    assert!(Form::parse_urlencoded("a=1&k=a+%FF") == Err(DecodeError::InvalidCharacter('\u{fffd}', 8)));
+    assert!(Form::parse_cookie("a=1; b=%E2%98") == Err(DecodeError::InvalidCharacter('\u{fffd}', 7)));
+    assert!(Form::parse_cookie("\u{263a}=\u{e9}; b=\u{e9}%E9") == Err(DecodeError::InvalidCharacter('\u{fffd}', 8)));
+
+    let cookie = Form::parse_cookie("comment1=cooking%20MCs; userdata=a+b;admin").unwrap();
+    assert!(cookie.get("comment1") == Some("cooking MCs"));
+    assert!(cookie.get("userdata") == Some("a+b"));
+    assert!(cookie.get("admin") == Some(""));
+}
+
+#[test]
+fn form_encoding_round_trip() {
+    fn equality_after_round_trip(pairs: Vec<(String, String)>) -> bool {
+        let mut form = Form::new();
+        for &(ref k, ref v) in &pairs {
+            form.push(k, v);
+        }
+        Form::parse_urlencoded(&form.to_urlencoded()) == Ok(form.clone()) &&
+            Form::parse_cookie(&form.to_cookie()) == Ok(form)
+    }
+    quickcheck(equality_after_round_trip as fn(Vec<(String, String)>) -> bool);
 }
 
 #[test]
 fn test_profile_for() {
-    let encoding = "email=foo@bar.com&uid=10&role=user";
+    let encoding = "email=foo%40bar.com&uid=10&role=user";
     assert!(profile_for("foo@bar.com") == encoding);
 }
 
 #[test]
 fn test_profile_for_is_sane() {
-    let encoding = "email=foo@bar.comroleadmin&uid=10&role=user";
+    let encoding = "email=foo%40bar.com%26role%3Dadmin&uid=10&role=user";
     assert!(profile_for("foo@bar.com&role=admin") == encoding);
+    let profile = Form::parse_urlencoded(encoding).unwrap();
+    assert!(profile.get("email") == Some("foo@bar.com&role=admin"));
+    assert!(profile.get_all("role") == vec!["user"]);
 }
 
 #[test]
 fn challenge_13() {
-    let padded = pad_pkcs7(&string_to_raw("admin"), 16);
-    let profile = "not-a-suspicious-user@foo.com";
-
-    // construct a string such that we cut off "...&role=" in its own block
-    let (split1, split2) = profile.split_at(10);
-    let mut modified_profile = vec![];
-    modified_profile.extend(split1.bytes());
-    modified_profile.extend(&padded);
-    modified_profile.extend(split2.bytes());
+    let key = random_aes();
+    let oracle = create_profile_oracle(Aes128::new(&key));
 
-    let new_profile = profile_for(&raw_to_string(&modified_profile));
-    let (key, encrypted) = encrypt_profile(&new_profile);
+    // metacharacters are escaped, but whole blocks can still be spliced.
+    // "email=mee%40bar.com&uid=10&role=" is exactly two blocks.
+    let first = oracle(&b"mee@bar.com".to_vec());
 
-    // split the blocks up.
-    let first = &encrypted[..16];
-    let admin = &encrypted[16..32];
-    let second = &encrypted[32..64];
+    // "email=abc%40foo." then "admin&uid=10&rol" then "e=user" and padding.
+    let second = oracle(&b"abc@foo.admin".to_vec());
 
-    // re-splice.
+    // re-splice, leaving a second role for whoever only reads the first.
     let mut result = vec![];
-    result.extend(first);
-    result.extend(second);
-    result.extend(admin);
+    result.extend(&first[..32]);
+    result.extend(&second[16..]);
 
-    let new_user = decrypt_profile(&result, &key);
-
-    let string_tuple = |k: &str, v: &str| (k.to_string(), v.to_string());
-    assert!(new_user[0] == string_tuple("email", "not-a-suspicious-user@foo.com"));
-    assert!(new_user[1] == string_tuple("uid", "10"));
-    assert!(new_user[2] == string_tuple("role", "admin"));
+    let new_user = decrypt_profile(&result, &Aes128::new(&key)).unwrap();
+    assert!(new_user.get("email") == Some("mee@bar.com"));
+    assert!(new_user.get("uid") == Some("10"));
+    assert!(new_user.get("role") == Some("admin"));
+    assert!(new_user.get_all("role") == vec!["admin", "user"]);
 }
 
 #[test]
 fn challenge_14() {
     let mystery_string = "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkgaGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBqdXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUgYnkK";
-    let Base64(mystery) = string_to_base64(&mystery_string);
-    let key = random_string(16);
-    let oracle = create_harder_oracle(&mystery, &key);
+    let Base64(mystery) = mystery_string.parse().unwrap();
+    let key = random_aes();
+    let oracle = create_harder_oracle(&mystery, Aes128::new(&key));
     let result = decrypt_ecb(oracle);
     // we may be decrypting past the known string due to the
     // ciphertext being rounded up to the nearest block size.
-    assert!(result[..mystery.len()] == raw_to_string(&mystery));
+    assert!(result[..mystery.len()] == mystery[..]);
 }
 
 #[test]
 fn challenge_15() {
-    assert!(strip_padding("ICE ICE BABY\x04\x04\x04\x04") == Some("ICE ICE BABY".to_string()));
-    assert!(strip_padding("ICE ICE BABY\x05\x05\x05\x05") == None);
-    assert!(strip_padding("ICE ICE BABY\x01\x02\x03\x04") == None);
+    assert!(Pkcs7.unpad(b"ICE ICE BABY\x04\x04\x04\x04", 16) == Ok(&b"ICE ICE BABY"[..]));
+    assert!(Pkcs7.unpad(b"ICE ICE BABY\x05\x05\x05\x05", 16) == Err(PaddingError::BadByte(11)));
+    assert!(Pkcs7.unpad(b"ICE ICE BABY\x01\x02\x03\x04", 16) == Err(PaddingError::BadByte(12)));
+}
+
+#[test]
+fn padding_schemes() {
+    let schemes: Vec<Box<Padding>> = vec![Box::new(Pkcs7), Box::new(AnsiX923), Box::new(Iso7816),
+                                          Box::new(Iso10126)];
+    for scheme in schemes.iter() {
+        for n in 0..40 {
+            let v: Vec<u8> = (0..n).map(|i| i as u8 + 1).collect();
+            let padded = scheme.pad(&v, 16);
+            assert!(padded.len() % 16 == 0 && padded.len() > v.len());
+            assert!(scheme.unpad(&padded, 16) == Ok(&v[..]));
+        }
+    }
+
+    // aligned input gets a whole block of padding.
+    assert!(Pkcs7.pad(b"YELLOW SUBMARINE", 16) == [&b"YELLOW SUBMARINE"[..], &[16; 16]].concat());
+    assert!(Pkcs7.unpad(&[16; 16], 16) == Ok(&b""[..]));
+    assert!(AnsiX923.pad(b"ICE", 8) == b"ICE\x00\x00\x00\x00\x05".to_vec());
+    assert!(Iso7816.pad(b"ICE", 8) == b"ICE\x80\x00\x00\x00\x00".to_vec());
+    assert!(ZeroPadding.pad(b"ICE", 8) == b"ICE\x00\x00\x00\x00\x00".to_vec());
+    assert!(ZeroPadding.pad(b"ICE ICE!", 8) == b"ICE ICE!".to_vec());
+    assert!(ZeroPadding.unpad(b"ICE\x00\x00\x00\x00\x00", 8) == Ok(&b"ICE"[..]));
+
+    // each kind of failure is reported differently.
+    assert!(Pkcs7.unpad(b"", 16) == Err(PaddingError::BadLength(0)));
+    assert!(Pkcs7.unpad(b"ICE ICE\x01", 16) == Err(PaddingError::BadLength(8)));
+    let schemes: Vec<&Padding> = vec![&Pkcs7, &AnsiX923, &Iso7816, &Iso10126, &ZeroPadding];
+    for scheme in schemes {
+        assert!(scheme.unpad(b"ICE ICE\x01", 0) == Err(PaddingError::BadLength(8)));
+    }
+    assert!(Pkcs7.unpad(b"ICE ICE\x00", 8) == Err(PaddingError::BadPadLength(0)));
+    assert!(Pkcs7.unpad(b"ICE ICE\x09", 8) == Err(PaddingError::BadPadLength(9)));
+    assert!(AnsiX923.unpad(b"ICE\x00\x01\x00\x00\x05", 8) == Err(PaddingError::BadByte(4)));
+    assert!(Iso7816.unpad(b"ICE\x80\x00\x01\x00\x00", 8) == Err(PaddingError::BadByte(5)));
+    assert!(Iso7816.unpad(&[0; 8], 8) == Err(PaddingError::MissingMarker));
+    assert!(Iso10126.unpad(b"ICE\x12\x34\x56\x78\x05", 8) == Ok(&b"ICE"[..]));
 }
 
 #[test]
 fn test_is_admin() {
     let iv = random_aes();
-    let key = "YELLOW SUBMARINE";
+    let key = b"YELLOW SUBMARINE";
     let legit = "foo=bar;admin=true;bar=z;padding";
-    let encrypted1 = encrypt_aes_cbc(&iv, &string_to_raw(legit), &key);
-    assert!(is_admin(&iv, &encrypted1, &key) == true);
+    let encrypted1 = encrypt_aes_cbc(&iv, &string_to_raw(legit).unwrap(), key).unwrap();
+    assert!(is_admin(&iv, &encrypted1, &Aes128::new(key)) == Ok(true));
     let not_legit = "foo=bar;bar=baz";
-    let encrypted2 = encrypt_aes_cbc(&iv, &string_to_raw(not_legit), &key);
-    assert!(is_admin(&iv, &encrypted2, &key) == false);
+    let encrypted2 = encrypt_aes_cbc(&iv, &string_to_raw(not_legit).unwrap(), key).unwrap();
+    assert!(is_admin(&iv, &encrypted2, &Aes128::new(key)) == Ok(false));
+    let broken = "foo=%FF;admin=true";
+    let encrypted3 = encrypt_aes_cbc(&iv, &string_to_raw(broken).unwrap(), key).unwrap();
+    assert!(is_admin(&iv, &encrypted3, &Aes128::new(key)).is_err());
 }
 
 #[test]
 fn challenge_16() {
-    let iv = random_aes();
-    let key = random_string(16);
-    let repeating: Vec<u8> = iter::repeat('x' as u8).take(32).collect();
-    let mut userdata = create_userdata(&iv, &raw_to_string(&repeating), &key);
-    let mut i = 32;
-    let search = ";admin=true;";
-    for ch in search.chars() {
-        userdata[i] = userdata[i] ^ ('x' as u8) ^ (ch as u8);
-        i += 1;
+    // the flipped block decrypts to random bytes, which now and then form an
+    // escape that isn't utf-8. a new key gives new bytes.
+    for _ in 0..10 {
+        let iv = random_aes();
+        let cipher = Aes128::new(&random_aes());
+        let repeating: Vec<u8> = iter::repeat('x' as u8).take(32).collect();
+        let mut userdata = create_userdata(&iv, &raw_to_string(&repeating), &cipher);
+        let mut i = 32;
+        let search = ";admin=true;";
+        for ch in search.chars() {
+            userdata[i] = userdata[i] ^ ('x' as u8) ^ (ch as u8);
+            i += 1;
+        }
+        let decrypted = cbc_decrypt(&cipher, &iv, &userdata);
+        let result = raw_to_string(&decrypted);
+        assert!(result.contains(search));
+        match is_admin(&iv, &userdata, &cipher) {
+            Ok(admin) => {
+                assert!(admin);
+                return;
+            }
+            Err(_) => continue
+        }
     }
-    let decrypted = decrypt_aes_cbc(&iv, &userdata, &key);
-    let result = raw_to_string(&decrypted);
-    assert!(result.contains(search));
-    assert!(is_admin(&iv, &userdata, &key));
+    panic!("the cookie never parsed");
+}
+
+#[test]
+fn block_view() {
+    let mut v: Vec<u8> = (0..4).collect();
+    v.extend(vec![0xaa; 4]);
+    v.extend((0..4).collect::<Vec<u8>>());
+    v.extend(vec![0xaa; 4]);
+    v.extend(vec![0xaa; 4]);
+    v.extend(vec![0xff; 2]);
+    assert!(repeated_blocks(&v, 4) == vec![vec![0, 2], vec![1, 3, 4]]);
+    let view = format!("{}", BlockView::new(&v, 4));
+    assert!(view == concat!("   0  00000000  00010203  [A]\n",
+                            "   1  00000004  aaaaaaaa  [B]\n",
+                            "   2  00000008  00010203  [A]\n",
+                            "   3  0000000c  aaaaaaaa  [B]\n",
+                            "   4  00000010  aaaaaaaa  [B]\n",
+                            "   5  00000014  ffff\n"));
+
+    // challenge 8's ciphertext has a block repeated four times.
+    let tests = read_hexlines_file("data/8.txt");
+    let ecb = test_for_aes_ecb(&tests).unwrap();
+    let groups = repeated_blocks(&ecb, 16);
+    assert!(groups.len() == 1 && groups[0].len() == 4);
+}
+
+#[test]
+fn block_diff() {
+    let iv = random_aes();
+    let cipher = Aes128::new(&random_aes());
+    let original = create_userdata(&iv, "xxxxxxxxxxxxxxxx", &cipher);
+    let mut flipped = original.clone();
+    flipped[16] ^= 1;
+    flipped.truncate(original.len() - 16);
+    let changes = block_changes(&original, &flipped, 16);
+    assert!(changes[0] == BlockChange::Same);
+    assert!(changes[1] == BlockChange::Changed);
+    assert!(changes[2..changes.len() - 1].iter().all(|&c| c == BlockChange::Same));
+    assert!(changes[changes.len() - 1] == BlockChange::Removed);
+
+    let diff = format!("{}", BlockDiff::new(&[1, 2, 3, 4], &[1, 2, 3, 5, 6], 2));
+    assert!(diff == concat!("     0  00000000  0102\n",
+                            "-    1  00000002  0304\n",
+                            "+    1  00000002  0305\n",
+                            "+    2  00000004  06\n"));
+}
+
+#[test]
+#[should_panic]
+fn block_changes_rejects_zero_block_size() {
+    block_changes(b"abc", b"abd", 0);
 }
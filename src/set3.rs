@@ -0,0 +1,207 @@
+
+use cipher::*;
+use conversion::*;
+use crypto::*;
+use keystream::*;
+use score::*;
+use set1::vanilla_lines;
+use util::*;
+
+#[test]
+fn challenge_18() {
+    let Base64(block) = "L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==".parse().unwrap();
+    let result = aes_ctr(&CTR_CRYPTOPALS, &[0; 8], &block, b"YELLOW SUBMARINE").unwrap();
+    assert!(raw_to_string(&result) == "Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ");
+    assert!(aes_ctr(&CTR_CRYPTOPALS, &result, &result, b"YELLOW SUBMARINE") ==
+            Err(CipherError::InvalidIvLength(result.len())));
+}
+
+#[test]
+fn ctr_nist_vectors() {
+    // NIST SP 800-38A F.5.1, whose initial counter block is f0f1...feff.
+    let Hex(key) = "2b7e151628aed2a6abf7158809cf4f3c".parse().unwrap();
+    let Hex(nonce) = "f0f1f2f3f4f5f6f7f8f9fafb".parse().unwrap();
+    let Hex(plaintext) = concat!("6bc1bee22e409f96e93d7e117393172a",
+                                 "ae2d8a571e03ac9c9eb76fac45af8e51",
+                                 "30c81c46a35ce411e5fbc1191a0a52ef",
+                                 "f69f2445df4f9b17ad2b417be66c3710").parse().unwrap();
+    let expected = concat!("874d6191b620e3261bef6864990db6ce",
+                           "9806f66b7970fdff8617187bb9fffdff",
+                           "5ae4df3edbd5d35e5b4f09020db03eab",
+                           "1e031dda2fbe03d1792170a0f3009cee");
+    let mut ctr = Ctr::new(Aes128::new(&key), &CTR_NIST, &nonce).unwrap();
+    ctr.seek(0xfcfdfeff * 16);
+    let mut data = plaintext.clone();
+    ctr.apply_keystream(&mut data);
+    assert!(Hex(data.clone()) == expected);
+
+    ctr.seek(0xfcfdfeff * 16);
+    ctr.apply_keystream(&mut data);
+    assert!(data == plaintext);
+}
+
+#[test]
+fn nist_feedback_modes() {
+    // NIST SP 800-38A F.3.13, F.3.7 and F.4.1, all with the same key and IV.
+    let Hex(key) = "2b7e151628aed2a6abf7158809cf4f3c".parse().unwrap();
+    let Hex(iv) = "000102030405060708090a0b0c0d0e0f".parse().unwrap();
+    let Hex(plaintext) = concat!("6bc1bee22e409f96e93d7e117393172a",
+                                 "ae2d8a571e03ac9c9eb76fac45af8e51",
+                                 "30c81c46a35ce411e5fbc1191a0a52ef",
+                                 "f69f2445df4f9b17ad2b417be66c3710").parse().unwrap();
+
+    let encrypted = encrypt_aes_cfb(&iv, &plaintext, &key).unwrap();
+    assert!(Hex(encrypted.clone()) == concat!("3b3fd92eb72dad20333449f8e83cfb4a",
+                                              "c8a64537a0b3a93fcde3cdad9f1ce58b",
+                                              "26751f67a3cbb140b1808cf187a4f4df",
+                                              "c04b05357c5d1c0eeac4c66f9ff7f2e6"));
+    assert!(decrypt_aes_cfb(&iv, &encrypted, &key).unwrap() == plaintext);
+
+    let encrypted = encrypt_aes_cfb8(&iv, &plaintext[..18].to_vec(), &key).unwrap();
+    assert!(Hex(encrypted.clone()) == "3b79424c9c0dd436bace9e0ed4586a4f32b9");
+    assert!(decrypt_aes_cfb8(&iv, &encrypted, &key).unwrap() == plaintext[..18].to_vec());
+
+    let encrypted = aes_ofb(&iv, &plaintext, &key).unwrap();
+    assert!(Hex(encrypted.clone()) == concat!("3b3fd92eb72dad20333449f8e83cfb4a",
+                                              "7789508d16918f03f53c52dac54ed825",
+                                              "9740051e9c5fecf64344f7a82260edcc",
+                                              "304c6528f659c77866a510d9c1d6ae5e"));
+    assert!(aes_ofb(&iv, &encrypted, &key).unwrap() == plaintext);
+
+    // the stream-like modes keep a trailing partial block.
+    let partial = plaintext[..20].to_vec();
+    let full = encrypt_aes_cfb(&iv, &plaintext, &key).unwrap();
+    assert!(encrypt_aes_cfb(&iv, &partial, &key).unwrap() == full[..20].to_vec());
+    assert!(aes_ofb(&iv, &partial, &key).unwrap() == encrypted[..20].to_vec());
+
+    // 800-38A has no PCBC, so check it against its definition.
+    let encrypted = encrypt_aes_pcbc(&iv, &plaintext, &key).unwrap();
+    let cipher = Aes128::new(&key);
+    let mut previous = iv.clone();
+    for (p, c) in plaintext.chunks(16).zip(encrypted.chunks(16)) {
+        let mut block = xor(p, &previous);
+        cipher.encrypt_block(&mut block);
+        assert!(&block[..] == c);
+        previous = xor(p, c);
+    }
+    assert!(decrypt_aes_pcbc(&iv, &encrypted, &key).unwrap() == plaintext);
+
+    let short = iv[..8].to_vec();
+    assert!(encrypt_aes_cfb(&short, &plaintext, &key) == Err(CipherError::InvalidIvLength(8)));
+    assert!(aes_ofb(&iv, &plaintext, &key[..8]) == Err(CipherError::InvalidKeyLength(8)));
+    assert!(decrypt_aes_pcbc(&vec![], &plaintext, &key) == Err(CipherError::InvalidIvLength(0)));
+}
+
+#[test]
+fn mode_error_propagation() {
+    let key = random_aes();
+    let iv = random_aes();
+    let plaintext: Vec<u8> = (0..96).collect();
+    let changed = |a: &Vec<u8>| -> Vec<usize> {
+        a.chunks(16).zip(plaintext.chunks(16)).enumerate()
+            .filter(|&(_, (x, y))| x != y).map(|(i, _)| i).collect()
+    };
+
+    // flip one bit in the second ciphertext block.
+    let mut cbc = encrypt_aes_cbc(&iv, &plaintext, &key).unwrap();
+    cbc[16] ^= 1;
+    assert!(changed(&decrypt_aes_cbc(&iv, &cbc, &key).unwrap()) == vec![1, 2]);
+    let mut cfb = encrypt_aes_cfb(&iv, &plaintext, &key).unwrap();
+    cfb[16] ^= 1;
+    assert!(changed(&decrypt_aes_cfb(&iv, &cfb, &key).unwrap()) == vec![1, 2]);
+    let mut ofb = aes_ofb(&iv, &plaintext, &key).unwrap();
+    ofb[16] ^= 1;
+    let decrypted = aes_ofb(&iv, &ofb, &key).unwrap();
+    assert!(changed(&decrypted) == vec![1] && decrypted[16] == plaintext[16] ^ 1);
+    let mut pcbc = encrypt_aes_pcbc(&iv, &plaintext, &key).unwrap();
+    pcbc[16] ^= 1;
+    assert!(changed(&decrypt_aes_pcbc(&iv, &pcbc, &key).unwrap()) == vec![1, 2, 3, 4, 5]);
+
+    // a byte error in CFB-8 garbles the rest of the register's worth.
+    let mut cfb8 = encrypt_aes_cfb8(&iv, &plaintext, &key).unwrap();
+    cfb8[20] ^= 1;
+    let decrypted = decrypt_aes_cfb8(&iv, &cfb8, &key).unwrap();
+    assert!(decrypted[..20] == plaintext[..20] && decrypted[37..] == plaintext[37..]);
+
+    // swapping two PCBC blocks leaves the rest intact, which is why Kerberos v5 dropped it.
+    let mut swapped = encrypt_aes_pcbc(&iv, &plaintext, &key).unwrap();
+    for i in 16..32 {
+        swapped.swap(i, i + 16);
+    }
+    assert!(changed(&decrypt_aes_pcbc(&iv, &swapped, &key).unwrap()) == vec![1, 2]);
+}
+
+#[test]
+fn ctr_seekable_keystream() {
+    let key = random_aes();
+    let data: Vec<u8> = (0..100).collect();
+    let whole = aes_ctr(&CTR_CRYPTOPALS, &[7; 8], &data, &key).unwrap();
+
+    // any split, in place, gives the same result as one pass.
+    let mut ctr = Ctr::new(Aes128::new(&key), &CTR_CRYPTOPALS, &[7; 8]).unwrap();
+    let mut pieces = data.clone();
+    for chunk in pieces.chunks_mut(7) {
+        ctr.apply_keystream(chunk);
+    }
+    assert!(pieces == whole && ctr.position() == 100);
+
+    // decrypt from the middle without touching what comes before.
+    let mut tail = whole[37..].to_vec();
+    ctr.seek(37);
+    ctr.apply_keystream(&mut tail);
+    assert!(tail == data[37..].to_vec());
+
+    // the counter wraps within its width, so block 256 reuses block 0.
+    let narrow = CtrLayout { nonce_size: 15, counter_size: 1, endian: Endian::Big };
+    let mut ctr = Ctr::new(Aes128::new(&key), &narrow, &[0; 15]).unwrap();
+    let mut first = vec![0; 16];
+    let mut wrapped = vec![0; 16];
+    ctr.apply_keystream(&mut first);
+    ctr.seek(256 * 16);
+    ctr.apply_keystream(&mut wrapped);
+    assert!(first == wrapped);
+
+    // so does the position, rather than overflowing.
+    let mut ctr = Ctr::new(Aes128::new(&key), &CTR_CRYPTOPALS, &[7; 8]).unwrap();
+    let mut end = vec![0; 10];
+    ctr.seek(u64::max_value() - 3);
+    ctr.apply_keystream(&mut end);
+    assert!(ctr.position() == 6 && end[4..] == xor(&whole, &data)[..6]);
+
+    // layouts that don't fit the cipher are errors, checked before the nonce.
+    let wide = CtrLayout { nonce_size: 4, counter_size: 12, endian: Endian::Big };
+    let short = CtrLayout { nonce_size: 8, counter_size: 4, endian: Endian::Big };
+    assert!(Ctr::new(Aes128::new(&key), &wide, &[0; 4]).err() == Some(CipherError::InvalidLayout));
+    assert!(Ctr::new(Aes128::new(&key), &short, &[0; 8]).err() == Some(CipherError::InvalidLayout));
+    assert!(aes_ctr(&short, &[0; 3], &data, &key) == Err(CipherError::InvalidLayout));
+    assert!(aes_ctr(&CTR_NIST, &[0; 8], &data, &key) == Err(CipherError::InvalidIvLength(8)));
+}
+
+#[test]
+fn ctr_bit_flipping() {
+    let key = random_aes();
+    let plaintext = b"comment1=cooking%20MCs;userdata=xadminxtrue".to_vec();
+    let mut encrypted = aes_ctr(&CTR_CRYPTOPALS, &[0; 8], &plaintext, &key).unwrap();
+    encrypted[32] ^= b'x' ^ b';';
+    encrypted[38] ^= b'x' ^ b'=';
+    let result = aes_ctr(&CTR_CRYPTOPALS, &[0; 8], &encrypted, &key).unwrap();
+    assert!(result == b"comment1=cooking%20MCs;userdata=;admin=true".to_vec());
+}
+
+#[test]
+fn fixed_nonce_ctr() {
+    let key = random_aes();
+    let lines = vanilla_lines();
+    let ciphertexts = lines.iter().map(|l| {
+        aes_ctr(&CTR_CRYPTOPALS, &[0; 8], l, &key).unwrap()
+    }).collect();
+
+    // every line shares the keystream, so the well covered columns at the
+    // start of each line come out as plain English.
+    let mut pad = ManyTimePad::new(ciphertexts);
+    pad.solve(&ChiSquared::english());
+    let recovered = pad.plaintexts();
+    assert!(recovered[0].starts_with(b"I'm back and I'm ringin'"));
+    assert!(recovered[5].starts_with(b"Vanilla's on the mike"));
+    assert!(recovered[8].starts_with(b"To just let it flow"));
+}
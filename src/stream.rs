@@ -0,0 +1,343 @@
+use std::io::prelude::*;
+use std::io;
+
+use conversion::*;
+
+const BUFFER_SIZE: usize = 4096;
+
+fn invalid_data(e: DecodeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// decodes hex from an underlying reader, skipping any whitespace.
+///
+/// unlike `decode_hex`, an odd number of digits is an error rather than an
+/// implicit leading zero since the length isn't known up front.
+///
+/// bytes decoded before an error are returned first. after that, every read
+/// returns the error.
+pub struct HexReader<R> {
+    inner: R,
+    buffer: Vec<u8>,
+    offset: usize,
+    high: Option<(u8, usize)>,
+    done: bool,
+    error: Option<DecodeError>,
+}
+
+impl<R: Read> HexReader<R> {
+    pub fn new(inner: R) -> HexReader<R> {
+        HexReader {
+            inner: inner,
+            buffer: vec![0; BUFFER_SIZE],
+            offset: 0,
+            high: None,
+            done: false,
+            error: None,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for HexReader<R> {
+    fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written == 0 && !self.done && dst.len() > 0 {
+            // two hex digits per output byte, so never read more than we can emit.
+            let wanted = ::std::cmp::min(self.buffer.len(), dst.len() * 2);
+            let n = try!(self.inner.read(&mut self.buffer[..wanted]));
+            if n == 0 {
+                self.done = true;
+                if let Some((_, offset)) = self.high {
+                    self.error = Some(DecodeError::TruncatedGroup(offset));
+                }
+                break;
+            }
+            for i in 0..n {
+                let c = self.buffer[i] as char;
+                let offset = self.offset;
+                self.offset += 1;
+                if c.is_whitespace() {
+                    continue;
+                }
+                let digit = match c.to_digit(16) {
+                    Some(d) => d as u8,
+                    None => {
+                        self.done = true;
+                        self.error = Some(DecodeError::InvalidCharacter(c, offset));
+                        break;
+                    }
+                };
+                match self.high.take() {
+                    Some((high, _)) => {
+                        dst[written] = 16 * high + digit;
+                        written += 1;
+                    }
+                    None => self.high = Some((digit, offset))
+                }
+            }
+        }
+        if written == 0 {
+            if let Some(e) = self.error {
+                return Err(invalid_data(e));
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// decodes Base64 from an underlying reader according to a `Base64Config`.
+///
+/// as with `HexReader`, bytes decoded before an error are returned first and
+/// every read after that returns the error.
+pub struct Base64Reader<R> {
+    inner: R,
+    config: Base64Config,
+    buffer: Vec<u8>,
+    offset: usize,
+    group: [u8; 4],
+    group_length: usize,
+    group_start: usize,
+    // number of '=' seen and where the first one was.
+    padding: usize,
+    padding_start: usize,
+    decoded: Vec<u8>,
+    position: usize,
+    done: bool,
+    error: Option<DecodeError>,
+}
+
+impl<R: Read> Base64Reader<R> {
+    pub fn new(inner: R) -> Base64Reader<R> {
+        Base64Reader::with_config(inner, &BASE64_STANDARD)
+    }
+
+    pub fn with_config(inner: R, config: &Base64Config) -> Base64Reader<R> {
+        Base64Reader {
+            inner: inner,
+            config: *config,
+            buffer: vec![0; BUFFER_SIZE],
+            offset: 0,
+            group: [0; 4],
+            group_length: 0,
+            group_start: 0,
+            padding: 0,
+            padding_start: 0,
+            decoded: Vec::with_capacity(BUFFER_SIZE),
+            position: 0,
+            done: false,
+            error: None,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn emit_group(&mut self) {
+        let e = self.group;
+        self.decoded.push((e[0] << 2) | (e[1] >> 4));
+        if self.group_length > 2 {
+            self.decoded.push((e[1] << 4) | (e[2] >> 2));
+        }
+        if self.group_length > 3 {
+            self.decoded.push((e[2] << 6) | e[3]);
+        }
+        self.group_length = 0;
+    }
+
+    fn push(&mut self, c: char, offset: usize) -> Result<(), DecodeError> {
+        if self.config.ignore_whitespace && c.is_whitespace() {
+            return Ok(());
+        }
+        if c == '=' {
+            if self.config.padding == PaddingPolicy::Omitted || self.group_length < 2 ||
+                self.group_length + self.padding == 4 {
+                return Err(DecodeError::InvalidPadding(c, offset));
+            }
+            if self.padding == 0 {
+                self.padding_start = offset;
+            }
+            self.padding += 1;
+            return Ok(());
+        }
+        // padding may only appear at the end of the final group.
+        if self.padding > 0 {
+            return Err(DecodeError::InvalidPadding('=', self.padding_start));
+        }
+        if self.group_length == 0 {
+            self.group_start = offset;
+        }
+        self.group[self.group_length] = match self.config.alphabet.find(c) {
+            Some(n) => n as u8,
+            None => return Err(DecodeError::InvalidCharacter(c, offset))
+        };
+        self.group_length += 1;
+        if self.group_length == 4 {
+            self.emit_group();
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), DecodeError> {
+        if self.padding > 0 {
+            if self.group_length + self.padding != 4 {
+                return Err(DecodeError::InvalidPadding('=', self.padding_start));
+            }
+            self.emit_group();
+            return Ok(());
+        }
+        match self.group_length {
+            0 => Ok(()),
+            1 => Err(DecodeError::TruncatedGroup(self.group_start)),
+            _ if self.config.padding == PaddingPolicy::Required =>
+                Err(DecodeError::TruncatedGroup(self.group_start)),
+            _ => {
+                self.emit_group();
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for Base64Reader<R> {
+    fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.decoded.len() && !self.done {
+            self.decoded.clear();
+            self.position = 0;
+            let n = try!(self.inner.read(&mut self.buffer));
+            if n == 0 {
+                self.done = true;
+                self.error = self.finish().err();
+                break;
+            }
+            for i in 0..n {
+                let c = self.buffer[i] as char;
+                let offset = self.offset;
+                self.offset += 1;
+                if let Err(e) = self.push(c, offset) {
+                    self.done = true;
+                    self.error = Some(e);
+                    break;
+                }
+            }
+        }
+        if self.position == self.decoded.len() {
+            if let Some(e) = self.error {
+                return Err(invalid_data(e));
+            }
+        }
+        let available = &self.decoded[self.position..];
+        let n = ::std::cmp::min(available.len(), dst.len());
+        dst[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// encodes everything written to it as hex.
+pub struct HexWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> HexWriter<W> {
+    pub fn new(inner: W) -> HexWriter<W> {
+        HexWriter { inner: inner }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for HexWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        try!(self.inner.write_all(encode_hex(buf).as_bytes()));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// encodes everything written to it as Base64 according to a `Base64Config`.
+///
+/// the final group can only be written once the input is known to be
+/// complete, so call `finish` when done. dropping the writer finishes it but
+/// discards any error.
+pub struct Base64Writer<W: Write> {
+    inner: Option<W>,
+    config: Base64Config,
+    pending: Vec<u8>,
+    column: usize,
+}
+
+impl<W: Write> Base64Writer<W> {
+    pub fn new(inner: W) -> Base64Writer<W> {
+        Base64Writer::with_config(inner, &BASE64_STANDARD)
+    }
+
+    pub fn with_config(inner: W, config: &Base64Config) -> Base64Writer<W> {
+        Base64Writer {
+            inner: Some(inner),
+            config: *config,
+            pending: Vec::with_capacity(3),
+            column: 0,
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        try!(self.write_final());
+        Ok(self.inner.take().unwrap())
+    }
+
+    fn write_encoded(&mut self, v: &[u8]) -> io::Result<()> {
+        let unwrapped = Base64Config { line_width: None, .. self.config };
+        let encoded = unwrapped.encode(v);
+        let mut result = String::with_capacity(encoded.len());
+        for c in encoded.chars() {
+            if let Some(width) = self.config.line_width {
+                if width > 0 && self.column == width {
+                    result.push_str(self.config.line_ending);
+                    self.column = 0;
+                }
+            }
+            result.push(c);
+            self.column += 1;
+        }
+        self.inner.as_mut().unwrap().write_all(result.as_bytes())
+    }
+
+    fn write_final(&mut self) -> io::Result<()> {
+        let pending = self.pending.clone();
+        self.pending.clear();
+        self.write_encoded(&pending)
+    }
+}
+
+impl<W: Write> Write for Base64Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut v = self.pending.clone();
+        v.extend_from_slice(buf);
+        let complete = v.len() - v.len() % 3;
+        try!(self.write_encoded(&v[..complete]));
+        self.pending = v[complete..].to_vec();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for Base64Writer<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.write_final();
+        }
+    }
+}
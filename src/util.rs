@@ -1,10 +1,11 @@
 #![allow(dead_code)]
 
-use std::{iter, cmp};
-use std::collections::{HashMap};
+use std::cmp;
+use std::cmp::Ordering;
 use rand::{thread_rng, Rng};
 
 use conversion::*;
+use form::*;
 
 pub fn random_string(n: usize) -> String {
     let mut rng = thread_rng();
@@ -18,18 +19,66 @@ pub fn random_string(n: usize) -> String {
     }).collect()
 }
 
-pub fn xor(v1: &Vec<u8>, v2: &Vec<u8>) -> Vec<u8> {
+/// the result is as long as the shorter input.
+pub fn xor(v1: &[u8], v2: &[u8]) -> Vec<u8> {
     v1.iter().zip(v2).map(|(x, y)| x ^ y).collect()
 }
 
-pub fn xor_one(v: &Vec<u8>, val: u8) -> Vec<u8> {
+/// like `xor`, but `None` unless both are the same length.
+pub fn xor_checked(v1: &[u8], v2: &[u8]) -> Option<Vec<u8>> {
+    if v1.len() != v2.len() {
+        return None;
+    }
+    Some(xor(v1, v2))
+}
+
+/// xors `src` into `dst` in place. panics unless both are the same length.
+pub fn xor_into(dst: &mut [u8], src: &[u8]) {
+    assert!(dst.len() == src.len());
+    for (x, y) in dst.iter_mut().zip(src) {
+        *x ^= *y;
+    }
+}
+
+/// xors a repeating `key` into `dst` in place, starting from the beginning of
+/// the key. an empty key leaves `dst` as it is.
+pub fn xor_cycle(dst: &mut [u8], key: &[u8]) {
+    if key.is_empty() {
+        return;
+    }
+    for chunk in dst.chunks_mut(key.len()) {
+        for (x, y) in chunk.iter_mut().zip(key) {
+            *x ^= *y;
+        }
+    }
+}
+
+pub fn xor_one(v: &[u8], val: u8) -> Vec<u8> {
     v.iter().map(|x| x ^ val).collect()
 }
 
-pub fn xor_key(v: &Vec<u8>, key: &str) -> Vec<u8> {
-    let k: String = iter::repeat(key).take(v.len()).collect();
-    let v2 = k.chars().map(|x: char| x as u8).collect();
-    xor(v, &v2)
+/// `key` is Latin-1 like the keys `break_repeating_key_xor` finds. as with
+/// `xor_cycle`, an empty key leaves `v` as it is.
+pub fn xor_key(v: &[u8], key: &str) -> Result<Vec<u8>, DecodeError> {
+    Ok(xor_key_bytes(v, &try!(string_to_raw(key))))
+}
+
+/// as with `xor_cycle`, an empty key leaves `v` as it is.
+pub fn xor_key_bytes(v: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut result = v.to_vec();
+    xor_cycle(&mut result, key);
+    result
+}
+
+/// the shortest key that repeats to form `key`, e.g. "ICE" for "ICEICE".
+pub fn shortest_period(key: &[u8]) -> &[u8] {
+    let n = key.len();
+    for p in 1..n {
+        if n % p == 0 && key.chunks(p).all(|c| c == &key[..p]) {
+            return &key[..p];
+        }
+    }
+    key
 }
 
 pub fn is_printable(c: char) -> bool {
@@ -37,8 +86,9 @@ pub fn is_printable(c: char) -> bool {
     (v >= 10 && v <= 11) || (v >= 32 && v < 127)
 }
 
-pub fn transpose(chunks: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    let len = chunks.iter().map(|c| c.len()).max().unwrap();
+/// no chunks give no columns.
+pub fn transpose(chunks: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let len = chunks.iter().map(|c| c.len()).max().unwrap_or(0);
     let mut vecs: Vec<Vec<u8>> = (0..len).map(|_| Vec::new()).collect();
     for c1 in chunks.iter() {
         for (j, &c2) in c1.iter().enumerate() {
@@ -48,84 +98,31 @@ pub fn transpose(chunks: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
     vecs
 }
 
-pub fn hamming(v1: &Vec<u8>, v2: &Vec<u8>) -> usize {
+/// the number of differing bits. panics unless both are the same length.
+pub fn hamming(v1: &[u8], v2: &[u8]) -> usize {
     assert!(v1.len() == v2.len());
-    let result = xor(&v1, &v2);
-    result.iter().fold(0, |accum, &x| {
-        let mut n = 0;
-        let mut elem = x;
-        while elem > 0 {
-            if (elem & 1) == 1 {
-                n += 1;
-            }
-            elem >>= 1;
-        }
-        accum + n
-    })
-}
-
-fn test_against(map: &HashMap<char, usize>, length: usize, c: char, threshold: f64, weight: usize) -> i64 {
-    let n = length as f64;
-    if let Some(&occurence) = map.get(&c) {
-        let freq = (occurence as f64) / n;
-        if freq > threshold {
-            weight as i64
-        } else {
-            (weight as f64 * (freq / threshold)) as i64
-        }
+    // eight bytes at a time so each popcount covers a whole word.
+    let split = v1.len() / 8 * 8;
+    let mut n = 0;
+    for (a, b) in v1[..split].chunks(8).zip(v2[..split].chunks(8)) {
+        n += (word(a) ^ word(b)).count_ones() as usize;
     }
-    else {
-        0
+    for (x, y) in v1[split..].iter().zip(&v2[split..]) {
+        n += (x ^ y).count_ones() as usize;
     }
+    n
 }
 
-pub fn english_probability(s: &str) -> i64 {
-    let mut p = 0;
-    let result: String = s.chars().filter(|&c| is_printable(c)).collect();
-    p += (result.len() as i64) * 2;
-    let mut histogram = HashMap::new();
-    for c in s.chars() {
-        let counter = histogram.entry(c).or_insert(0);
-        *counter += 1;
-    }
-    let n = s.len();
-    p += test_against(&histogram, n, ' ', 0.130, 20);
-    p += test_against(&histogram, n, 'e', 0.127, 20);
-    p += test_against(&histogram, n, 't', 0.091, 18);
-    p += test_against(&histogram, n, 'a', 0.081, 15);
-    p += test_against(&histogram, n, 'o', 0.075, 12);
-    p += test_against(&histogram, n, 'i', 0.070, 10);
-    p += test_against(&histogram, n, 'n', 0.067, 9);
-    p += test_against(&histogram, n, 's', 0.063, 8);
-    p += test_against(&histogram, n, 'h', 0.061, 7);
-    p += test_against(&histogram, n, 'r', 0.060, 6);
-    p += test_against(&histogram, n, 'd', 0.043, 5);
-    p += test_against(&histogram, n, 'l', 0.040, 4);
-    // 'c', 0.02782
-    // 'u', 0.02758
-    // 'm', 0.02406
-    // 'w', 0.02361
-    // 'f', 0.02228
-    // 'g', 0.02015
-    // 'y', 0.01974
-    // 'p', 0.01929
-    // 'b', 0.01492
-    // 'v', 0.00978
-    // 'k', 0.00772
-    // 'j', 0.00153
-    // 'x', 0.00150
-    // 'q', 0.00095
-    // 'z', 0.00074
-    p
-}
-
-pub fn pad_pkcs7(v: &Vec<u8>, n: usize) -> Vec<u8> {
-    assert!(n < 256);
-    let l = n - v.len();
-    let mut result = v.clone();
-    let mut suffix: Vec<u8> = iter::repeat(l as u8).take(l).collect();
-    result.append(&mut suffix);
-    result
+fn word(v: &[u8]) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(v);
+    u64::from_ne_bytes(bytes)
+}
+
+/// orders scores highest first, for sorting candidates best first. NaN
+/// compares equal to everything.
+pub fn best_first(a: f64, b: f64) -> Ordering {
+    b.partial_cmp(&a).unwrap_or(Ordering::Equal)
 }
 
 pub fn gcd(a: usize, b: usize) -> usize {
@@ -136,35 +133,14 @@ pub fn gcd(a: usize, b: usize) -> usize {
     }
 }
 
-pub fn gcd_array(lengths: &Vec<usize>) -> usize {
+pub fn gcd_array(lengths: &[usize]) -> usize {
     lengths.iter().fold(0, |accum, &v| gcd(accum, v))
 }
 
-pub fn key_value(query: &str) -> Vec<(String, String)> {
-    // no validation or error checking.
-    query.split('&').map(|x| {
-        let result: Vec<&str> = x.split('=').collect();
-        (result[0].to_string(),
-         result[1].to_string())
-    }).collect()
-}
-
 pub fn profile_for(email: &str) -> String {
-    let uid = 10;
-    let role = "user";
-    let email = email.clone();
-    let email = email.replace("&", "");
-    let email = email.replace("=", "");
-    format!("email={}&uid={}&role={}", email, uid, role).clone()
-}
-
-pub fn strip_padding(s: &str) -> Option<String> {
-    let n = s.len();
-    for i in 1..n {
-        let padding = &s[n-i..n];
-        if padding.bytes().all(|x| x == i as u8) {
-            return Some(s[..n-i].to_string())
-        }
-    }
-    None
+    let mut profile = Form::new();
+    profile.push("email", email);
+    profile.push("uid", "10");
+    profile.push("role", "user");
+    profile.to_urlencoded()
 }